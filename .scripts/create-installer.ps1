@@ -4,8 +4,9 @@ if (-not (Test-Path -Path "./dist/komorebi-switcher.exe")) {
     exit 1
 }
 
-# Copy the komorebi-switcher.exe to the installer directory
+# Copy the komorebi-switcher.exe and its debug symbols to the installer directory
 Copy-Item -Force "./dist/komorebi-switcher.exe" "./installer/komorebi-switcher.exe"
+Copy-Item -Force "./dist/komorebi_switcher.pdb" "./installer/komorebi_switcher.pdb"
 
 # Copy the icon.ico to the installer directory
 Copy-Item -Force "./assets/icon.ico" "./installer/icon.ico"
@@ -18,4 +19,5 @@ Move-Item -Force "./installer/komorebi-switcher-setup.exe" "./dist/komorebi-swit
 
 # Remove artifacts
 Remove-Item -Force "./installer/komorebi-switcher.exe"
+Remove-Item -Force "./installer/komorebi_switcher.pdb"
 Remove-Item -Force "./installer/icon.ico"
@@ -15,6 +15,19 @@ const MANIFEST: &str = r#"<?xml version="1.0" encoding="utf-8"?>
         />
     </dependentAssembly>
   </dependency>
+
+  <!-- Windows only supports layered child windows, used by the CPU renderer,
+       for apps declaring Windows 8 or later -->
+  <compatibility xmlns="urn:schemas-microsoft-com:compatibility.v1">
+    <application>
+      <!-- Windows 10 and 11 -->
+      <supportedOS Id="{8e0f7a12-bfb3-4fe8-b9a5-48fd50a15a9a}"/>
+      <!-- Windows 8.1 -->
+      <supportedOS Id="{1f676c76-80e1-4239-95bb-83d0f6d0da78}"/>
+      <!-- Windows 8 -->
+      <supportedOS Id="{4a2f28e3-53b9-4441-ba9c-d69d4a4a6e38}"/>
+    </application>
+  </compatibility>
 </assembly>
 "#;
 
@@ -0,0 +1,414 @@
+use anyhow::Context;
+use color::{AlphaColor, Hsl, Srgb};
+
+pub type Color = AlphaColor<Srgb>;
+
+/// System colors that can be referenced by name in color expressions.
+///
+/// Unset colors make the expressions referencing them resolve to nothing.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct SystemColors {
+	pub accent: Option<Color>,
+	/// Lighter variants of the accent color, from the lightest to the darkest.
+	pub accent_light: [Option<Color>; 3],
+	/// Darker variants of the accent color, from the lightest to the darkest.
+	pub accent_dark: [Option<Color>; 3],
+	pub foreground: Option<Color>,
+	pub background: Option<Color>,
+}
+
+/// CSS color functions, parsed as literals.
+const CSS_FUNCTIONS: [&str; 10] = [
+	"rgb", "rgba", "hsl", "hsla", "hwb", "lab", "lch", "oklab", "oklch", "color",
+];
+
+impl SystemColors {
+	const NAMES: [&str; 9] = [
+		"accent",
+		"accent-light1",
+		"accent-light2",
+		"accent-light3",
+		"accent-dark1",
+		"accent-dark2",
+		"accent-dark3",
+		"foreground",
+		"background",
+	];
+
+	fn get(&self, name: &str) -> Option<Color> {
+		match name {
+			"accent" => self.accent,
+			"accent-light1" => self.accent_light[0],
+			"accent-light2" => self.accent_light[1],
+			"accent-light3" => self.accent_light[2],
+			"accent-dark1" => self.accent_dark[0],
+			"accent-dark2" => self.accent_dark[1],
+			"accent-dark3" => self.accent_dark[2],
+			"foreground" => self.foreground,
+			"background" => self.background,
+			_ => None,
+		}
+	}
+}
+
+/// A parsed color expression.
+///
+/// Supported expressions:
+/// - any CSS color, i.e `#4CC2FF`, `rgba(180, 173, 170, 0.6)` or `red`
+/// - system colors: `accent`, `accent-light1..3`, `accent-dark1..3`, `foreground`
+///   and `background`
+/// - `mix(a, b, amount)`: mixes `amount` of `b` into `a`, defaults to 50%
+/// - `alpha(a, amount)`: sets the opacity of `a`
+/// - `lighten(a, amount)` and `darken(a, amount)`: adjusts the lightness of `a`
+///
+/// Amounts are numbers between 0 and 1, or percentages.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ColorExpr {
+	Literal(Color),
+	System(String),
+	Mix(Box<ColorExpr>, Box<ColorExpr>, f32),
+	Alpha(Box<ColorExpr>, f32),
+	Lighten(Box<ColorExpr>, f32),
+	Darken(Box<ColorExpr>, f32),
+}
+
+impl ColorExpr {
+	pub fn parse(input: &str) -> anyhow::Result<Self> {
+		let mut parser = Parser { input, pos: 0 };
+		let expr = parser.expr()?;
+
+		parser.skip_whitespace();
+		let rest = parser.rest();
+		if !rest.is_empty() {
+			anyhow::bail!("Unexpected `{rest}` at position {}", parser.pos);
+		}
+
+		Ok(expr)
+	}
+
+	/// Evaluates the expression against the given system colors,
+	/// returns `None` if it references an unavailable system color.
+	pub fn eval(&self, system: &SystemColors) -> Option<Color> {
+		match self {
+			ColorExpr::Literal(color) => Some(*color),
+			ColorExpr::System(name) => system.get(name),
+			ColorExpr::Mix(a, b, amount) => {
+				let (a, b) = (a.eval(system)?, b.eval(system)?);
+				Some(a.lerp_rect(b, *amount))
+			}
+			ColorExpr::Alpha(a, amount) => Some(a.eval(system)?.with_alpha(*amount)),
+			ColorExpr::Lighten(a, amount) => Some(adjust_lightness(a.eval(system)?, *amount)),
+			ColorExpr::Darken(a, amount) => Some(adjust_lightness(a.eval(system)?, -amount)),
+		}
+	}
+}
+
+/// Parses and evaluates a color expression, see [`ColorExpr`].
+pub fn resolve_color(input: &str, system: &SystemColors) -> Option<Color> {
+	ColorExpr::parse(input).ok()?.eval(system)
+}
+
+/// Adds `amount` to the HSL lightness of the color, negative amounts darken it.
+pub fn adjust_lightness(color: Color, amount: f32) -> Color {
+	color
+		.convert::<Hsl>()
+		.map_lightness(|l| (l + amount).clamp(0.0, 1.0))
+		.convert::<Srgb>()
+}
+
+struct Parser<'a> {
+	input: &'a str,
+	pos: usize,
+}
+
+impl Parser<'_> {
+	fn rest(&self) -> &str {
+		&self.input[self.pos..]
+	}
+
+	fn skip_whitespace(&mut self) {
+		let rest = self.rest();
+		self.pos += rest.len() - rest.trim_start().len();
+	}
+
+	fn expect(&mut self, c: char) -> anyhow::Result<()> {
+		self.skip_whitespace();
+		if !self.rest().starts_with(c) {
+			anyhow::bail!("Expected `{c}` at position {}", self.pos);
+		}
+		self.pos += c.len_utf8();
+		Ok(())
+	}
+
+	fn eat(&mut self, c: char) -> bool {
+		self.skip_whitespace();
+		let found = self.rest().starts_with(c);
+		if found {
+			self.pos += c.len_utf8();
+		}
+		found
+	}
+
+	fn ident(&self) -> &str {
+		let rest = self.rest();
+		let end = rest
+			.find(|c: char| !(c.is_ascii_alphanumeric() || c == '-' || c == '_'))
+			.unwrap_or(rest.len());
+		&rest[..end]
+	}
+
+	fn expr(&mut self) -> anyhow::Result<ColorExpr> {
+		self.skip_whitespace();
+
+		let ident = self.ident().to_ascii_lowercase();
+		let is_call = self.rest()[ident.len()..].trim_start().starts_with('(');
+
+		match ident.as_str() {
+			"mix" | "alpha" | "lighten" | "darken" if is_call => {
+				self.pos += ident.len();
+				self.expect('(')?;
+				let expr = self.function(&ident)?;
+				self.expect(')')?;
+				Ok(expr)
+			}
+			_ if !is_call && SystemColors::NAMES.contains(&ident.as_str()) => {
+				self.pos += ident.len();
+				Ok(ColorExpr::System(ident))
+			}
+			_ if is_call && !CSS_FUNCTIONS.contains(&ident.as_str()) => {
+				anyhow::bail!("Unknown function `{ident}` at position {}", self.pos)
+			}
+			_ => self.literal(),
+		}
+	}
+
+	fn function(&mut self, name: &str) -> anyhow::Result<ColorExpr> {
+		let arity = if name == "mix" { "2 or 3" } else { "2" };
+		let arity_error =
+			|pos| anyhow::anyhow!("`{name}` expects {arity} arguments, at position {pos}");
+
+		let a = Box::new(self.expr()?);
+		if !self.eat(',') {
+			return Err(arity_error(self.pos));
+		}
+
+		let expr = match name {
+			"mix" => {
+				let b = Box::new(self.expr()?);
+				let amount = if self.eat(',') { self.amount()? } else { 0.5 };
+				ColorExpr::Mix(a, b, amount)
+			}
+			"alpha" => ColorExpr::Alpha(a, self.amount()?),
+			"lighten" => ColorExpr::Lighten(a, self.amount()?),
+			"darken" => ColorExpr::Darken(a, self.amount()?),
+			_ => unreachable!("unknown function `{name}`"),
+		};
+
+		if self.eat(',') {
+			return Err(arity_error(self.pos - 1));
+		}
+
+		Ok(expr)
+	}
+
+	/// Parses a number between 0 and 1, or a percentage.
+	fn amount(&mut self) -> anyhow::Result<f32> {
+		self.skip_whitespace();
+
+		let rest = self.rest();
+		let end = rest
+			.find(|c: char| !(c.is_ascii_digit() || c == '.' || c == '-'))
+			.unwrap_or(rest.len());
+
+		let mut amount: f32 = rest[..end]
+			.parse()
+			.with_context(|| format!("Expected a number at position {}", self.pos))?;
+		self.pos += end;
+
+		if self.eat('%') {
+			amount /= 100.0;
+		}
+
+		if !(0.0..=1.0).contains(&amount) {
+			anyhow::bail!("Amount {amount} is out of range, expected 0 - 1 or 0% - 100%");
+		}
+
+		Ok(amount)
+	}
+
+	fn literal(&mut self) -> anyhow::Result<ColorExpr> {
+		let (len, color) = color::parse_color_prefix(self.rest())
+			.map_err(|e| anyhow::anyhow!("Invalid color at position {}: {e}", self.pos))?;
+		self.pos += len;
+		Ok(ColorExpr::Literal(color.to_alpha_color::<Srgb>()))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn system() -> SystemColors {
+		SystemColors {
+			accent: Some(Color::new([0.0, 0.0, 1.0, 1.0])),
+			foreground: Some(Color::new([1.0, 1.0, 1.0, 1.0])),
+			..Default::default()
+		}
+	}
+
+	fn eval(input: &str) -> Color {
+		ColorExpr::parse(input)
+			.unwrap_or_else(|e| panic!("failed to parse `{input}`: {e}"))
+			.eval(&system())
+			.unwrap_or_else(|| panic!("failed to eval `{input}`"))
+	}
+
+	fn assert_color(input: &str, expected: [f32; 4]) {
+		let actual = eval(input).components;
+		let close = actual
+			.iter()
+			.zip(expected)
+			.all(|(a, e)| (a - e).abs() < 1e-3);
+		assert!(
+			close,
+			"`{input}` evaluated to {actual:?}, expected {expected:?}"
+		);
+	}
+
+	fn parse_error(input: &str) -> String {
+		match ColorExpr::parse(input) {
+			Ok(expr) => panic!("`{input}` parsed as {expr:?}, expected an error"),
+			Err(e) => e.to_string(),
+		}
+	}
+
+	#[test]
+	fn literals() {
+		assert_color("#ff0000", [1.0, 0.0, 0.0, 1.0]);
+		assert_color("#FF000080", [1.0, 0.0, 0.0, 128.0 / 255.0]);
+		assert_color("red", [1.0, 0.0, 0.0, 1.0]);
+		assert_color("rgba(255, 0, 0, 0.5)", [1.0, 0.0, 0.0, 0.5]);
+		assert_color("hsl(0, 100%, 50%)", [1.0, 0.0, 0.0, 1.0]);
+		assert_color("  #00ff00  ", [0.0, 1.0, 0.0, 1.0]);
+	}
+
+	#[test]
+	fn system_colors() {
+		assert_eq!(
+			ColorExpr::parse("accent").unwrap(),
+			ColorExpr::System("accent".to_string())
+		);
+		assert_eq!(
+			ColorExpr::parse("Accent").unwrap(),
+			ColorExpr::System("accent".to_string())
+		);
+		assert_color("accent", [0.0, 0.0, 1.0, 1.0]);
+
+		// Unset system colors resolve to nothing
+		let expr = ColorExpr::parse("mix(accent-light2, red)").unwrap();
+		assert_eq!(expr.eval(&system()), None);
+	}
+
+	#[test]
+	fn nesting() {
+		assert_color("alpha(mix(accent, #fff, 50%), 0.5)", [0.5, 0.5, 1.0, 0.5]);
+		assert_color("darken(lighten(red, 20%), 20%)", [1.0, 0.0, 0.0, 1.0]);
+		// Mixed with premultiplied alpha, so transparent colors don't tint
+		assert_color(
+			"mix(alpha(red, 0), alpha(blue, 1), 0.5)",
+			[0.0, 0.0, 1.0, 0.5],
+		);
+	}
+
+	#[test]
+	fn precedence() {
+		// Function names are only functions when called, system color names
+		// take precedence over CSS named colors
+		assert!(ColorExpr::parse("mix").is_err());
+		assert!(matches!(
+			ColorExpr::parse("lighten(foreground, 0.1)").unwrap(),
+			ColorExpr::Lighten(expr, _) if *expr == ColorExpr::System("foreground".to_string())
+		));
+		assert!(matches!(
+			ColorExpr::parse("MIX ( red , blue )").unwrap(),
+			ColorExpr::Mix(_, _, amount) if amount == 0.5
+		));
+	}
+
+	#[test]
+	fn amounts() {
+		assert_color("alpha(red, 0.25)", [1.0, 0.0, 0.0, 0.25]);
+		assert_color("alpha(red, 25%)", [1.0, 0.0, 0.0, 0.25]);
+		assert!(parse_error("alpha(red, 1.5)").contains("out of range"));
+		assert!(parse_error("alpha(red, 150%)").contains("out of range"));
+		assert!(parse_error("alpha(red, half)").contains("Expected a number"));
+	}
+
+	#[test]
+	fn mix() {
+		assert_color("mix(black, white)", [0.5, 0.5, 0.5, 1.0]);
+		assert_color("mix(black, white, 25%)", [0.25, 0.25, 0.25, 1.0]);
+		assert_color("mix(black, white, 0)", [0.0, 0.0, 0.0, 1.0]);
+		assert_color("mix(black, white, 1)", [1.0, 1.0, 1.0, 1.0]);
+	}
+
+	#[test]
+	fn lighten_and_darken() {
+		// Red has an HSL lightness of 50%
+		assert_color("lighten(red, 25%)", [1.0, 0.5, 0.5, 1.0]);
+		assert_color("darken(red, 25%)", [0.5, 0.0, 0.0, 1.0]);
+
+		// Hue and saturation are kept
+		assert_color("lighten(hsl(120, 50%, 50%), 0.1)", [0.4, 0.8, 0.4, 1.0]);
+		assert_color("darken(hsl(120, 50%, 50%), 0.1)", [0.2, 0.6, 0.2, 1.0]);
+
+		// Lightness is clamped
+		assert_color("lighten(red, 100%)", [1.0, 1.0, 1.0, 1.0]);
+		assert_color("darken(red, 100%)", [0.0, 0.0, 0.0, 1.0]);
+
+		// Alpha is kept
+		assert_color("lighten(rgba(255, 0, 0, 0.5), 25%)", [1.0, 0.5, 0.5, 0.5]);
+	}
+
+	#[test]
+	fn unknown_function() {
+		assert_eq!(
+			parse_error("brighten(red, 0.1)"),
+			"Unknown function `brighten` at position 0"
+		);
+		assert_eq!(
+			parse_error("mix(red, shade(blue, 0.1))"),
+			"Unknown function `shade` at position 9"
+		);
+	}
+
+	#[test]
+	fn bad_arity() {
+		assert_eq!(
+			parse_error("alpha(red)"),
+			"`alpha` expects 2 arguments, at position 9"
+		);
+		assert_eq!(
+			parse_error("lighten(red, 0.1, 0.2)"),
+			"`lighten` expects 2 arguments, at position 16"
+		);
+		assert_eq!(
+			parse_error("mix(red)"),
+			"`mix` expects 2 or 3 arguments, at position 7"
+		);
+		assert_eq!(
+			parse_error("mix(red, blue, 0.5, 0.5)"),
+			"`mix` expects 2 or 3 arguments, at position 18"
+		);
+	}
+
+	#[test]
+	fn trailing_input() {
+		assert_eq!(parse_error("red blue"), "Unexpected `blue` at position 4");
+		assert_eq!(
+			parse_error("alpha(red, 0.5))"),
+			"Unexpected `)` at position 15"
+		);
+		assert_eq!(parse_error("alpha(red, 0.5"), "Expected `)` at position 14");
+	}
+}
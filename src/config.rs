@@ -1,9 +1,12 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::PathBuf;
 
 use anyhow::Context;
 use serde::{Deserialize, Serialize};
 
+use crate::komorebi::Workspace;
+use crate::theme::ThemeColors;
+
 fn default_width() -> i32 {
 	200
 }
@@ -12,21 +15,512 @@ fn default_height() -> i32 {
 	40
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+/// A font family, or a list of font families in fallback order,
+/// used to render glyphs missing from the first family.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum FontFamily {
+	Single(String),
+	List(Vec<String>),
+}
+
+impl FontFamily {
+	/// Gets the font families in fallback order,
+	/// a single family can also be a comma separated list.
+	pub fn families(&self) -> Vec<&str> {
+		let families = match self {
+			FontFamily::Single(family) => std::slice::from_ref(family),
+			FontFamily::List(families) => families.as_slice(),
+		};
+
+		families
+			.iter()
+			.flat_map(|f| f.split(','))
+			.map(str::trim)
+			.filter(|f| !f.is_empty())
+			.collect()
+	}
+
+	/// Parses font families from a text field, as a list if the text has
+	/// more than one comma separated family, `None` if it has none.
+	pub fn from_text(text: &str) -> Option<Self> {
+		let families: Vec<&str> = text
+			.split(',')
+			.map(str::trim)
+			.filter(|f| !f.is_empty())
+			.collect();
+
+		let family = match families.as_slice() {
+			[] => return None,
+			[family] => FontFamily::Single(family.to_string()),
+			_ => FontFamily::List(families.into_iter().map(String::from).collect()),
+		};
+
+		Some(family)
+	}
+}
+
+impl std::fmt::Display for FontFamily {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			FontFamily::Single(family) => f.write_str(family),
+			FontFamily::List(families) => f.write_str(&families.join(", ")),
+		}
+	}
+}
+
+/// Visual style of the workspace indicator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, strum::Display)]
+#[serde(rename_all = "kebab-case")]
+pub enum IndicatorStyle {
+	/// A short line at the bottom of the button, wider for the focused workspace.
+	#[default]
+	#[strum(to_string = "Bottom line")]
+	BottomLine,
+	/// A short line at the top of the button, wider for the focused workspace.
+	#[strum(to_string = "Top line")]
+	TopLine,
+	/// A line at the bottom of the button, spanning the full width for the
+	/// focused workspace.
+	Underline,
+	/// A filled rounded background behind the button.
+	Pill,
+	/// A small dot at the bottom of the button.
+	Dot,
+	/// An outline around the button.
+	Box,
+	None,
+}
+
+impl IndicatorStyle {
+	#[cfg(windows)]
+	pub const ALL: [Self; 7] = [
+		Self::BottomLine,
+		Self::TopLine,
+		Self::Underline,
+		Self::Pill,
+		Self::Dot,
+		Self::Box,
+		Self::None,
+	];
+}
+
+/// Where the switcher is placed in the taskbar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, strum::Display)]
+#[serde(rename_all = "kebab-case")]
+pub enum Anchor {
+	/// At the absolute `x` and `y` position in the taskbar, in physical pixels.
+	#[default]
+	Absolute,
+	/// At the left edge of the taskbar, or the top edge of vertical taskbars.
+	Left,
+	/// At the center of the taskbar.
+	Center,
+	/// At the right edge of the taskbar, or the bottom edge of vertical taskbars.
+	Right,
+	/// Right after the Start button, or the left (top) edge if it can't be found.
+	#[strum(to_string = "After Start button")]
+	AfterStart,
+	/// Right before the system tray, or the right (bottom) edge if it can't be found.
+	#[strum(to_string = "Before system tray")]
+	BeforeTray,
+}
+
+impl Anchor {
+	#[cfg(windows)]
+	pub const ALL: [Self; 6] = [
+		Self::Absolute,
+		Self::Left,
+		Self::Center,
+		Self::Right,
+		Self::AfterStart,
+		Self::BeforeTray,
+	];
+}
+
+/// Backend used to render the switcher and settings windows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, strum::Display)]
+#[serde(rename_all = "kebab-case")]
+pub enum Renderer {
+	/// The GPU, falling back to the CPU if it can't be used.
+	#[default]
+	Auto,
+	/// The GPU through DirectX 12, failing to start if it can't be used.
+	#[strum(to_string = "GPU")]
+	Gpu,
+	/// The CPU, for machines without DirectX 12 (i.e VMs or RDP sessions).
+	#[strum(to_string = "CPU")]
+	Cpu,
+}
+
+impl Renderer {
+	#[cfg(windows)]
+	pub const ALL: [Self; 3] = [Self::Auto, Self::Gpu, Self::Cpu];
+}
+
+/// Easing function of the animations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, strum::Display)]
+#[serde(rename_all = "kebab-case")]
+#[strum(serialize_all = "title_case")]
+pub enum Easing {
+	Linear,
+	#[default]
+	SineOut,
+	SineInOut,
+	QuadOut,
+	QuadInOut,
+	CubicOut,
+	CubicInOut,
+	ExpoOut,
+	BackOut,
+	ElasticOut,
+	BounceOut,
+}
+
+impl Easing {
+	#[cfg(windows)]
+	pub const ALL: [Self; 11] = [
+		Self::Linear,
+		Self::SineOut,
+		Self::SineInOut,
+		Self::QuadOut,
+		Self::QuadInOut,
+		Self::CubicOut,
+		Self::CubicInOut,
+		Self::ExpoOut,
+		Self::BackOut,
+		Self::ElasticOut,
+		Self::BounceOut,
+	];
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct AnimationsConfig {
+	/// Slides the active indicator from the previously focused workspace
+	/// to the newly focused one.
+	#[serde(default)]
+	pub sliding_indicator: bool,
+	/// Duration of the animations in milliseconds,
+	/// unset uses the default duration of each animation.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub duration: Option<u32>,
+	#[serde(default)]
+	pub easing: Easing,
+	/// Disables all animations,
+	/// they are also disabled if the OS is set to reduce motion.
+	#[serde(default)]
+	pub reduce_motion: bool,
+}
+
+/// Format of the log files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, strum::Display)]
+#[serde(rename_all = "kebab-case")]
+pub enum LogFormat {
+	#[default]
+	Plain,
+	/// One JSON object per line.
+	#[strum(to_string = "JSON")]
+	Json,
+}
+
+impl LogFormat {
+	#[cfg(windows)]
+	pub const ALL: [Self; 2] = [Self::Plain, Self::Json];
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LoggingConfig {
+	/// Level filter, i.e `info` or `info,komorebi_switcher=trace`,
+	/// the `KOMOREBI_SWITCHER_LOG` environment variable takes precedence over it.
+	#[serde(default = "default_log_level")]
+	pub level: String,
+	/// Directory of the log files, defaults to `komorebi-switcher/logs` in the data directory.
+	/// Requires a restart to take effect.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub directory: Option<PathBuf>,
+	/// Log files older than this are removed on startup, 0 keeps them forever.
+	#[serde(default = "default_log_retention_days")]
+	pub retention_days: u32,
+	/// Maximum size of all log files in megabytes, the oldest ones are
+	/// removed on startup when exceeded, 0 means no limit.
+	#[serde(default = "default_log_max_size")]
+	pub max_size: u64,
+	/// Requires a restart to take effect.
+	#[serde(default)]
+	pub format: LogFormat,
+}
+
+impl Default for LoggingConfig {
+	fn default() -> Self {
+		Self {
+			level: default_log_level(),
+			directory: None,
+			retention_days: default_log_retention_days(),
+			max_size: default_log_max_size(),
+			format: LogFormat::default(),
+		}
+	}
+}
+
+impl LoggingConfig {
+	pub fn is_default(&self) -> bool {
+		*self == Self::default()
+	}
+
+	/// Gets the directory of the log files, the configured one or the default one.
+	pub fn directory(&self) -> anyhow::Result<PathBuf> {
+		if let Some(directory) = &self.directory {
+			return Ok(directory.clone());
+		}
+
+		crate::utils::logs_dir()
+	}
+}
+
+fn default_log_level() -> String {
+	"debug".to_string()
+}
+
+fn default_log_retention_days() -> u32 {
+	30
+}
+
+fn default_log_max_size() -> u64 {
+	100
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
 pub struct ColorsConfig {
 	#[serde(default, skip_serializing_if = "Option::is_none")]
 	pub active_indicator: Option<String>,
 	#[serde(default, skip_serializing_if = "Option::is_none")]
 	pub busy_indicator: Option<String>,
+
+	/// Active indicator color used when the switcher's monitor is not focused.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub unfocused_active_indicator: Option<String>,
+	/// Opacity (0.0 - 1.0) applied to the indicators when the switcher's monitor
+	/// is not focused.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub unfocused_opacity: Option<f32>,
+	/// Color of a bar drawn along the top of the switcher on the focused monitor.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub focus_bar: Option<String>,
+
+	/// Overrides of the theme colors.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub text: Option<String>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub inactive_text: Option<String>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub empty_text: Option<String>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub hover_background: Option<String>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub focused_background: Option<String>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub stroke: Option<String>,
 }
 
 impl ColorsConfig {
 	pub fn is_empty(&self) -> bool {
-		self.active_indicator.is_none() && self.busy_indicator.is_none()
+		self.active_indicator.is_none()
+			&& self.busy_indicator.is_none()
+			&& self.unfocused_active_indicator.is_none()
+			&& self.unfocused_opacity.is_none()
+			&& self.focus_bar.is_none()
+			&& self.text.is_none()
+			&& self.inactive_text.is_none()
+			&& self.empty_text.is_none()
+			&& self.hover_background.is_none()
+			&& self.focused_background.is_none()
+			&& self.stroke.is_none()
+	}
+
+	/// Merges two colors configs, with `self` taking precedence over `other`.
+	fn merge(self, other: Self) -> Self {
+		Self {
+			active_indicator: self.active_indicator.or(other.active_indicator),
+			busy_indicator: self.busy_indicator.or(other.busy_indicator),
+			unfocused_active_indicator: self
+				.unfocused_active_indicator
+				.or(other.unfocused_active_indicator),
+			unfocused_opacity: self.unfocused_opacity.or(other.unfocused_opacity),
+			focus_bar: self.focus_bar.or(other.focus_bar),
+			text: self.text.or(other.text),
+			inactive_text: self.inactive_text.or(other.inactive_text),
+			empty_text: self.empty_text.or(other.empty_text),
+			hover_background: self.hover_background.or(other.hover_background),
+			focused_background: self.focused_background.or(other.focused_background),
+			stroke: self.stroke.or(other.stroke),
+		}
+	}
+
+	/// Gets the colors that override the theme colors.
+	#[cfg(target_os = "macos")]
+	fn theme_colors(&self) -> ThemeColors {
+		ThemeColors {
+			text: self.text.clone(),
+			inactive_text: self.inactive_text.clone(),
+			empty_text: self.empty_text.clone(),
+			hover_background: self.hover_background.clone(),
+			focused_background: self.focused_background.clone(),
+			stroke: self.stroke.clone(),
+			active_indicator: self.active_indicator.clone(),
+			busy_indicator: self.busy_indicator.clone(),
+		}
+	}
+}
+
+impl From<ThemeColors> for ColorsConfig {
+	fn from(colors: ThemeColors) -> Self {
+		Self {
+			text: colors.text,
+			inactive_text: colors.inactive_text,
+			empty_text: colors.empty_text,
+			hover_background: colors.hover_background,
+			focused_background: colors.focused_background,
+			stroke: colors.stroke,
+			active_indicator: colors.active_indicator,
+			busy_indicator: colors.busy_indicator,
+			..Default::default()
+		}
+	}
+}
+
+/// Sizes of the switcher buttons, in logical pixels.
+///
+/// Unset values fall back to the platform defaults.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct MetricsConfig {
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub min_width: Option<f32>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub min_height: Option<f32>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub padding_x: Option<f32>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub padding_y: Option<f32>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub corner_radius: Option<f32>,
+	/// Spacing between buttons.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub spacing: Option<f32>,
+	/// Indicator width for busy and empty workspaces.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub indicator_width: Option<f32>,
+	/// Indicator width for the focused workspace.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub indicator_active_width: Option<f32>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub indicator_height: Option<f32>,
+}
+
+impl MetricsConfig {
+	pub fn is_empty(&self) -> bool {
+		self.min_width.is_none()
+			&& self.min_height.is_none()
+			&& self.padding_x.is_none()
+			&& self.padding_y.is_none()
+			&& self.corner_radius.is_none()
+			&& self.spacing.is_none()
+			&& self.indicator_width.is_none()
+			&& self.indicator_active_width.is_none()
+			&& self.indicator_height.is_none()
+	}
+
+	/// Merges two metrics configs, with `self` taking precedence over `other`.
+	pub fn merge(self, other: Self) -> Self {
+		Self {
+			min_width: self.min_width.or(other.min_width),
+			min_height: self.min_height.or(other.min_height),
+			padding_x: self.padding_x.or(other.padding_x),
+			padding_y: self.padding_y.or(other.padding_y),
+			corner_radius: self.corner_radius.or(other.corner_radius),
+			spacing: self.spacing.or(other.spacing),
+			indicator_width: self.indicator_width.or(other.indicator_width),
+			indicator_active_width: self.indicator_active_width.or(other.indicator_active_width),
+			indicator_height: self.indicator_height.or(other.indicator_height),
+		}
+	}
+
+	/// Resets negative or non-finite values so they fall back to the defaults.
+	fn validate(&mut self) {
+		let values = [
+			("min_width", &mut self.min_width),
+			("min_height", &mut self.min_height),
+			("padding_x", &mut self.padding_x),
+			("padding_y", &mut self.padding_y),
+			("corner_radius", &mut self.corner_radius),
+			("spacing", &mut self.spacing),
+			("indicator_width", &mut self.indicator_width),
+			("indicator_active_width", &mut self.indicator_active_width),
+			("indicator_height", &mut self.indicator_height),
+		];
+
+		for (name, value) in values {
+			if value.is_some_and(|v| !v.is_finite() || v < 0.0) {
+				tracing::warn!("Invalid `metrics.{name}` value {value:?}, using default instead");
+				*value = None;
+			}
+		}
+	}
+}
+
+pub const FONT_SIZE_RANGE: std::ops::RangeInclusive<f32> = 4.0..=72.0;
+
+/// Command line used to start komorebi if `komorebi_start_command` isn't set.
+#[cfg(windows)]
+pub const DEFAULT_KOMOREBI_START_COMMAND: &str = "komorebic start";
+
+/// Resets the font size if it is outside of the supported range.
+fn validate_font_size(font_size: &mut Option<f32>) {
+	if font_size.is_some_and(|s| !FONT_SIZE_RANGE.contains(&s)) {
+		tracing::warn!("Invalid `font_size` value {font_size:?}, using default instead");
+		*font_size = None;
 	}
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct WorkspaceConfig {
+	/// Label format for this workspace, overrides `label_format`.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub label: Option<String>,
+	/// Glyph used for the `{icon}` label placeholder.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub icon: Option<String>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub active_indicator: Option<String>,
+	/// Hides this workspace from the switcher unless it is focused.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub hidden: Option<bool>,
+}
+
+impl WorkspaceConfig {
+	/// Merges two workspace configs, with `self` taking precedence over `other`.
+	fn merge(self, other: Self) -> Self {
+		Self {
+			label: self.label.or(other.label),
+			icon: self.icon.or(other.icon),
+			active_indicator: self.active_indicator.or(other.active_indicator),
+			hidden: self.hidden.or(other.hidden),
+		}
+	}
+}
+
+/// Finds the workspace config matching the workspace name,
+/// or its 1-based index as a fallback.
+fn find_workspace(
+	workspaces: &HashMap<String, WorkspaceConfig>,
+	workspace: &Workspace,
+) -> Option<WorkspaceConfig> {
+	workspaces
+		.get(&workspace.name)
+		.or_else(|| workspaces.get(&(workspace.index + 1).to_string()))
+		.cloned()
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct MonitorConfig {
 	#[serde(default, skip_serializing_if = "Option::is_none")]
 	pub show_layout_button: Option<bool>,
@@ -34,18 +528,45 @@ pub struct MonitorConfig {
 	pub hide_empty_workspaces: Option<bool>,
 
 	#[serde(default, skip_serializing_if = "Option::is_none")]
-	pub font_family: Option<String>,
+	pub font_family: Option<FontFamily>,
 	#[serde(default, skip_serializing_if = "Option::is_none")]
 	pub font_weight: Option<u16>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub font_size: Option<f32>,
+
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub label_format: Option<String>,
+
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub indicator_style: Option<IndicatorStyle>,
 
 	#[serde(default, skip_serializing_if = "ColorsConfig::is_empty")]
 	pub colors: ColorsConfig,
 
+	#[serde(default, skip_serializing_if = "MetricsConfig::is_empty")]
+	pub metrics: MetricsConfig,
+
 	#[serde(default = "default_true")]
 	pub auto_width: bool,
 	#[serde(default = "default_true")]
 	pub auto_height: bool,
 
+	/// Shows the switcher in a floating window instead of the taskbar, at `x`
+	/// and `y` relative to the monitor's top left corner in physical pixels.
+	/// Monitors without a taskbar always use a floating switcher.
+	#[serde(default)]
+	pub floating: bool,
+
+	/// Keeps the switcher visible in a floating window at the edge of the
+	/// monitor while an auto-hidden taskbar is hidden.
+	#[serde(default)]
+	pub keep_visible: bool,
+
+	/// Where the switcher is placed in the taskbar, `x` and `y` are offsets
+	/// from the anchor in logical pixels unless it is [`Anchor::Absolute`].
+	#[serde(default)]
+	pub anchor: Anchor,
+
 	#[serde(default)]
 	pub x: i32,
 	#[serde(default)]
@@ -55,6 +576,9 @@ pub struct MonitorConfig {
 	pub width: i32,
 	#[serde(default = "default_height")]
 	pub height: i32,
+
+	#[serde(skip_serializing_if = "HashMap::is_empty", default)]
+	pub workspaces: HashMap<String, WorkspaceConfig>,
 }
 
 impl Default for MonitorConfig {
@@ -64,18 +588,26 @@ impl Default for MonitorConfig {
 			hide_empty_workspaces: None,
 			font_family: None,
 			font_weight: None,
+			font_size: None,
+			label_format: None,
+			indicator_style: None,
 			colors: ColorsConfig::default(),
+			metrics: MetricsConfig::default(),
 			auto_width: true,
 			auto_height: true,
+			floating: false,
+			keep_visible: false,
+			anchor: Anchor::default(),
 			x: 0,
 			y: 0,
 			width: default_width(),
 			height: default_height(),
+			workspaces: HashMap::new(),
 		}
 	}
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
 pub struct Config {
 	#[serde(default)]
 	pub show_layout_button: bool,
@@ -83,18 +615,71 @@ pub struct Config {
 	pub hide_empty_workspaces: bool,
 
 	#[serde(default, skip_serializing_if = "Option::is_none")]
-	pub font_family: Option<String>,
+	pub font_family: Option<FontFamily>,
 	#[serde(default, skip_serializing_if = "Option::is_none")]
 	pub font_weight: Option<u16>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub font_size: Option<f32>,
+
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub label_format: Option<String>,
+
+	#[serde(default)]
+	pub indicator_style: IndicatorStyle,
+
+	#[serde(default)]
+	pub animations: AnimationsConfig,
+
+	/// Requires a restart to take effect.
+	#[serde(default)]
+	pub renderer: Renderer,
+
+	#[serde(default, skip_serializing_if = "LoggingConfig::is_default")]
+	pub logging: LoggingConfig,
+
+	/// Command line used to start komorebi from the context menu.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub komorebi_start_command: Option<String>,
+
+	/// Shows the debug overlay on startup.
+	#[serde(default, skip_serializing_if = "std::ops::Not::not")]
+	pub debug_overlay: bool,
+
+	/// Name of a built-in theme, or of a theme file in the themes directory.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub theme: Option<String>,
+
+	/// Uses the colors of the theme in komorebi's static config, if any.
+	#[serde(default)]
+	pub sync_komorebi_theme: bool,
 
 	#[serde(default, skip_serializing_if = "ColorsConfig::is_empty")]
 	pub colors: ColorsConfig,
 
+	#[serde(default, skip_serializing_if = "MetricsConfig::is_empty")]
+	pub metrics: MetricsConfig,
+
+	/// Maps workspace names, or regular expressions matching them, to icons.
+	#[serde(skip_serializing_if = "BTreeMap::is_empty", default)]
+	pub icons: BTreeMap<String, String>,
+
+	#[serde(skip_serializing_if = "HashMap::is_empty", default)]
+	pub workspaces: HashMap<String, WorkspaceConfig>,
+
 	#[serde(skip_serializing_if = "HashMap::is_empty", default)]
 	pub monitors: HashMap<String, MonitorConfig>,
 }
 
 impl Config {
+	/// Gets the command line used to start komorebi, falling back to
+	/// [`DEFAULT_KOMOREBI_START_COMMAND`].
+	#[cfg(windows)]
+	pub fn komorebi_start_command(&self) -> &str {
+		self.komorebi_start_command
+			.as_deref()
+			.unwrap_or(DEFAULT_KOMOREBI_START_COMMAND)
+	}
+
 	#[cfg(debug_assertions)]
 	pub const FILENAME: &'static str = "komorebi-switcher.debug.toml";
 	#[cfg(not(debug_assertions))]
@@ -114,7 +699,9 @@ impl Config {
 			tracing::info!("Loading config from {}", config_file.display());
 
 			let content = std::fs::read_to_string(&config_file)?;
-			toml::from_str(&content).map_err(Into::into)
+			let mut config: Config = toml::from_str(&content)?;
+			config.validate();
+			Ok(config)
 		} else {
 			tracing::info!(
 				"Config file not found at {}, using default config",
@@ -139,6 +726,26 @@ impl Config {
 		}
 	}
 
+	/// Loads only the `[logging]` section, before logging is initialized,
+	/// falling back to the defaults if the config can't be read.
+	pub fn load_logging() -> LoggingConfig {
+		#[derive(Deserialize)]
+		struct LoggingOnly {
+			#[serde(default)]
+			logging: LoggingConfig,
+		}
+
+		let Ok(config_file) = Self::path() else {
+			return LoggingConfig::default();
+		};
+
+		std::fs::read_to_string(config_file)
+			.ok()
+			.and_then(|content| toml::from_str::<LoggingOnly>(&content).ok())
+			.map(|config| config.logging)
+			.unwrap_or_default()
+	}
+
 	pub fn save(&self) -> anyhow::Result<()> {
 		let config_file = Self::path()?;
 
@@ -151,6 +758,17 @@ impl Config {
 		Ok(())
 	}
 
+	/// Resets invalid values so they fall back to the defaults.
+	fn validate(&mut self) {
+		validate_font_size(&mut self.font_size);
+		self.metrics.validate();
+
+		for monitor in self.monitors.values_mut() {
+			validate_font_size(&mut monitor.font_size);
+			monitor.metrics.validate();
+		}
+	}
+
 	#[allow(dead_code)]
 	pub fn get_monitor(&self, monitor_id: &str) -> MonitorConfig {
 		self.monitors.get(monitor_id).cloned().unwrap_or_default()
@@ -161,6 +779,105 @@ impl Config {
 		self.monitors.entry(monitor_id.to_string()).or_default()
 	}
 
+	/// Gets the config for the given monitor with the global config merged in,
+	/// its colors resolved in light or dark mode as in [`Config::resolve_colors`],
+	/// and the resolved config of each of its workspaces, as used by the switcher.
+	#[cfg_attr(not(windows), allow(unused))]
+	pub fn resolve_monitor(
+		&self,
+		monitor_id: &str,
+		workspaces: &[Workspace],
+		dark_mode: bool,
+		komorebi_theme: Option<&ThemeColors>,
+	) -> MonitorConfig {
+		let monitor = self.get_monitor(monitor_id);
+
+		MonitorConfig {
+			show_layout_button: monitor.show_layout_button.or(Some(self.show_layout_button)),
+			hide_empty_workspaces: monitor
+				.hide_empty_workspaces
+				.or(Some(self.hide_empty_workspaces)),
+			font_family: monitor.font_family.or_else(|| self.font_family.clone()),
+			font_weight: monitor.font_weight.or(self.font_weight),
+			font_size: monitor.font_size.or(self.font_size),
+			label_format: monitor.label_format.or_else(|| self.label_format.clone()),
+			indicator_style: monitor.indicator_style.or(Some(self.indicator_style)),
+			colors: self.resolve_colors(monitor_id, dark_mode, komorebi_theme),
+			metrics: monitor.metrics.merge(self.metrics.clone()),
+			workspaces: workspaces
+				.iter()
+				.map(|ws| (ws.name.clone(), self.get_workspace(monitor_id, ws)))
+				.collect(),
+			..monitor
+		}
+	}
+
+	/// Gets the config for the given workspace on the given monitor,
+	/// with monitor config taking precedence over global config.
+	pub fn get_workspace(&self, monitor_id: &str, workspace: &Workspace) -> WorkspaceConfig {
+		let global = find_workspace(&self.workspaces, workspace).unwrap_or_default();
+
+		let monitor = self
+			.monitors
+			.get(monitor_id)
+			.and_then(|m| find_workspace(&m.workspaces, workspace))
+			.unwrap_or_default();
+
+		monitor.merge(global)
+	}
+
+	/// Gets the colors for the given monitor in light or dark mode,
+	/// with monitor colors taking precedence over global colors,
+	/// global colors taking precedence over komorebi's theme colors if synced,
+	/// and komorebi's theme colors taking precedence over the theme colors.
+	pub fn resolve_colors(
+		&self,
+		monitor_id: &str,
+		dark_mode: bool,
+		komorebi_theme: Option<&ThemeColors>,
+	) -> ColorsConfig {
+		let theme = self.theme.as_deref().unwrap_or(crate::theme::DEFAULT_THEME);
+		let theme = crate::theme::get_theme(theme);
+
+		let monitor = self
+			.monitors
+			.get(monitor_id)
+			.map(|m| m.colors.clone())
+			.unwrap_or_default();
+
+		let komorebi_theme = komorebi_theme.filter(|_| self.sync_komorebi_theme);
+
+		monitor
+			.merge(self.colors.clone())
+			.merge(komorebi_theme.cloned().unwrap_or_default().into())
+			.merge(theme.colors(dark_mode).clone().into())
+	}
+
+	/// Gets the theme colors for the given monitor, see [`Config::resolve_colors`].
+	#[cfg(target_os = "macos")]
+	pub fn get_colors(
+		&self,
+		monitor_id: &str,
+		dark_mode: bool,
+		komorebi_theme: Option<&ThemeColors>,
+	) -> ThemeColors {
+		self.resolve_colors(monitor_id, dark_mode, komorebi_theme)
+			.theme_colors()
+	}
+
+	/// Gets the icon for the given workspace, with the workspace config taking
+	/// precedence over the `[icons]` table.
+	pub fn get_icon<'a>(
+		&'a self,
+		workspace_config: &'a WorkspaceConfig,
+		workspace: &Workspace,
+	) -> Option<&'a str> {
+		workspace_config
+			.icon
+			.as_deref()
+			.or_else(|| crate::icons::find_icon(&self.icons, &workspace.name))
+	}
+
 	#[allow(dead_code)]
 	pub fn set_monitor(&mut self, monitor_id: &str, config: MonitorConfig) {
 		self.monitors.insert(monitor_id.to_string(), config);
@@ -0,0 +1,112 @@
+//! Crash reports written by the panic hook, and offered on the next launch.
+
+use std::backtrace::Backtrace;
+use std::fmt::Write as _;
+use std::panic::PanicHookInfo;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use time::OffsetDateTime;
+
+/// File in the reports directory holding the name of the report of a crash
+/// that wasn't offered yet, see [`take_pending_report`].
+const PENDING_FILE: &str = "pending";
+
+/// Gets the directory crash reports are written to.
+pub fn reports_dir() -> anyhow::Result<PathBuf> {
+	let reports_dir = dirs::data_dir()
+		.context("Failed to get $data_dir path")?
+		.join("komorebi-switcher")
+		.join("crashes");
+	Ok(reports_dir)
+}
+
+/// Writes a crash report for the panic, marking it as pending if the panic
+/// is fatal so it is offered on the next launch.
+pub fn write_report(info: &PanicHookInfo<'_>, fatal: bool) -> anyhow::Result<PathBuf> {
+	let now = OffsetDateTime::now_utc();
+
+	let reports_dir = reports_dir()?;
+	std::fs::create_dir_all(&reports_dir)?;
+
+	let file_name = now.format(time::macros::format_description!(
+		"crash-[year]-[month]-[day]T[hour]-[minute]-[second]Z.txt"
+	))?;
+	let path = reports_dir.join(&file_name);
+
+	std::fs::write(&path, report(info, now))?;
+
+	if fatal {
+		std::fs::write(reports_dir.join(PENDING_FILE), &file_name)?;
+	}
+
+	Ok(path)
+}
+
+/// Gets the report of the last fatal crash if it wasn't offered yet,
+/// it won't be returned again.
+#[cfg(windows)]
+pub fn take_pending_report() -> Option<PathBuf> {
+	let reports_dir = reports_dir().ok()?;
+	let pending = reports_dir.join(PENDING_FILE);
+
+	let file_name = std::fs::read_to_string(&pending).ok()?;
+	if let Err(e) = std::fs::remove_file(&pending) {
+		tracing::error!("Failed to remove pending crash report marker: {e}");
+	}
+
+	let path = reports_dir.join(file_name.trim());
+	path.exists().then_some(path)
+}
+
+fn report(info: &PanicHookInfo<'_>, now: OffsetDateTime) -> String {
+	let message = info
+		.payload()
+		.downcast_ref::<&str>()
+		.copied()
+		.or_else(|| info.payload().downcast_ref::<String>().map(String::as_str))
+		.unwrap_or("Box<dyn Any>");
+
+	let location = info
+		.location()
+		.map(ToString::to_string)
+		.unwrap_or_else(|| "unknown".to_string());
+
+	let thread = std::thread::current();
+	let thread = thread.name().unwrap_or("unnamed");
+
+	let time = now
+		.format(&time::format_description::well_known::Rfc3339)
+		.unwrap_or_default();
+
+	let last_event = match crate::komorebi::last_event() {
+		Some((event, at)) => format!(
+			"{event} ({:.1}s before the crash)",
+			at.elapsed().as_secs_f32()
+		),
+		None => "None".to_string(),
+	};
+
+	let mut report = String::new();
+	let _ = writeln!(
+		report,
+		"{} v{}",
+		env!("CARGO_PKG_NAME"),
+		env!("CARGO_PKG_VERSION")
+	);
+	let _ = writeln!(report, "Debug build: {}", cfg!(debug_assertions));
+	let _ = writeln!(report, "Time: {time}");
+	let _ = writeln!(report, "Thread: {thread}");
+	let _ = writeln!(report, "Message: {message}");
+	let _ = writeln!(report, "Location: {location}");
+	let _ = writeln!(report, "Last komorebi event: {last_event}");
+
+	let _ = writeln!(report, "\nBacktrace:\n{}", Backtrace::force_capture());
+
+	let _ = writeln!(report, "\nRecent logs:");
+	for line in crate::logging::recent_lines() {
+		let _ = writeln!(report, "{line}");
+	}
+
+	report
+}
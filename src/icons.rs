@@ -0,0 +1,39 @@
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap};
+
+use regex::Regex;
+
+/// Finds the icon for the given workspace name from the `[icons]` config table.
+///
+/// Keys are matched exactly first, then as regular expressions that must match
+/// the whole name, in alphabetical order of the keys.
+pub fn find_icon<'a>(icons: &'a BTreeMap<String, String>, name: &str) -> Option<&'a str> {
+	if let Some(icon) = icons.get(name) {
+		return Some(icon);
+	}
+
+	icons
+		.iter()
+		.find(|(pattern, _)| regex_matches(pattern, name))
+		.map(|(_, icon)| icon.as_str())
+}
+
+/// Matches the name against the pattern, caching compiled patterns
+/// to avoid recompiling them on every frame.
+fn regex_matches(pattern: &str, name: &str) -> bool {
+	thread_local! {
+		static CACHE: RefCell<HashMap<String, Option<Regex>>> = RefCell::new(HashMap::new());
+	}
+
+	CACHE.with_borrow_mut(|cache| {
+		let regex = cache.entry(pattern.to_string()).or_insert_with(|| {
+			let regex = Regex::new(&format!("^(?:{pattern})$"));
+			if let Err(e) = &regex {
+				tracing::warn!("Invalid icon pattern '{pattern}': {e}");
+			}
+			regex.ok()
+		});
+
+		regex.as_ref().is_some_and(|r| r.is_match(name))
+	})
+}
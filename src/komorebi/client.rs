@@ -25,6 +25,13 @@ impl<T> MaybeRingOrVec<T> {
 			MaybeRingOrVec::Vec(vec) => vec.is_empty(),
 		}
 	}
+
+	pub fn len(&self) -> usize {
+		match self {
+			MaybeRingOrVec::Ring(ring) => ring.elements.len(),
+			MaybeRingOrVec::Vec(vec) => vec.len(),
+		}
+	}
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -50,6 +57,59 @@ impl KWorkspace {
 			&& self.monocle_container.is_none()
 			&& self.floating_windows.is_empty()
 	}
+
+	/// Number of windows in this workspace, including floating,
+	/// maximized and monocle windows.
+	pub fn window_count(&self) -> usize {
+		let containers: usize = self
+			.containers
+			.elements
+			.iter()
+			.map(container_window_count)
+			.sum();
+
+		let monocle = self
+			.monocle_container
+			.as_ref()
+			.map(container_window_count)
+			.unwrap_or_default();
+
+		let maximized = self.maximized_window.is_some() as usize;
+
+		containers + monocle + maximized + self.floating_windows.len()
+	}
+
+	/// Executable name of the focused window in this workspace, without the
+	/// `.exe` extension.
+	pub fn focused_app(&self) -> Option<String> {
+		let window = match (&self.maximized_window, &self.monocle_container) {
+			(Some(window), _) => Some(window),
+			(None, Some(container)) => container_focused_window(container),
+			(None, None) => self
+				.containers
+				.elements
+				.get(self.containers.focused_idx())
+				.and_then(container_focused_window),
+		}?;
+
+		let exe = window.get("exe")?.as_str()?;
+		let exe = exe.strip_suffix(".exe").unwrap_or(exe);
+		Some(exe.to_string())
+	}
+}
+
+fn container_window_count(container: &serde_json::Value) -> usize {
+	container
+		.pointer("/windows/elements")
+		.and_then(|w| w.as_array())
+		.map(|w| w.len())
+		.unwrap_or_default()
+}
+
+fn container_focused_window(container: &serde_json::Value) -> Option<&serde_json::Value> {
+	let windows = container.get("windows")?;
+	let focused = windows.get("focused")?.as_u64()? as usize;
+	windows.get("elements")?.get(focused)
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -116,6 +176,8 @@ pub enum KSocketMessage {
 	FocusMonitorWorkspaceNumber(usize, usize),
 	CycleLayout(KCycleDirection),
 	Query(KStateQuery),
+	ReloadConfiguration,
+	Stop,
 }
 
 #[derive(Debug, strum::Display, Serialize, Deserialize)]
@@ -130,9 +192,15 @@ pub enum KSocketEvent {
 	CycleFocusWorkspace,
 	CycleLayout,
 	ReloadConfiguration,
-	ReplaceConfiguration,
+	/// Replaces the configuration with the static config at the given path.
+	ReplaceConfiguration {
+		content: Option<PathBuf>,
+	},
 	CompleteConfiguration,
-	ReloadStaticConfiguration,
+	/// Reloads the static config at the given path.
+	ReloadStaticConfiguration {
+		content: Option<PathBuf>,
+	},
 	MoveContainerToMonitorNumber,
 	MoveContainerToMonitorWorkspaceNumber,
 	MoveContainerToNamedWorkspace,
@@ -161,13 +229,11 @@ pub enum KSocketEvent {
 #[derive(Debug, Deserialize)]
 #[serde(untagged)]
 pub enum KNotificationEvent {
-	#[allow(unused)]
 	Socket(KSocketEvent),
 }
 
 #[derive(Debug, Deserialize)]
 pub struct KNotification {
-	#[allow(unused)]
 	pub event: KNotificationEvent,
 	pub state: KState,
 }
@@ -1,13 +1,29 @@
 use std::io::{BufReader, Read};
+#[cfg(windows)]
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::Duration;
+#[cfg(windows)]
+use std::time::Instant;
 
+#[cfg(windows)]
+use anyhow::Context;
 use client::*;
+use serde::{Deserialize, Serialize};
+
+use crate::theme::ThemeColors;
 
 pub use crate::komorebi::client::KCycleDirection as CycleDirection;
+pub use crate::komorebi::stats::last_event;
+#[cfg(windows)]
+pub use crate::komorebi::stats::listener_stats;
 
 mod client;
+mod process;
+mod stats;
+mod theme;
 
-#[derive(Debug, Clone, Default, Copy)]
+#[derive(Debug, Clone, Default, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[allow(unused)]
 pub struct Rect {
 	pub left: i32,
@@ -40,21 +56,24 @@ impl Rect {
 	}
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Workspace {
 	pub name: String,
 	pub index: usize,
 	pub focused: bool,
 	pub is_empty: bool,
 	pub layout: String,
+	pub window_count: usize,
+	pub focused_app: Option<String>,
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 #[allow(unused)]
 pub struct Monitor {
 	pub name: String,
 	pub index: usize,
 	pub id: String,
+	pub focused: bool,
 	pub workspaces: Vec<Workspace>,
 	pub rect: Rect,
 }
@@ -64,7 +83,7 @@ impl Monitor {
 		self.workspaces.iter().find(|ws| ws.focused)
 	}
 
-	fn from(monitor: KMonitor, index: usize) -> Self {
+	fn from(monitor: KMonitor, index: usize, focused: bool) -> Self {
 		let workspaces = monitor
 			.workspaces
 			.elements
@@ -79,6 +98,8 @@ impl Monitor {
 					.clone()
 					.unwrap_or_else(|| (idx + 1).to_string()),
 				layout: workspace.layout.default.clone(),
+				window_count: workspace.window_count(),
+				focused_app: workspace.focused_app(),
 			})
 			.collect();
 
@@ -93,6 +114,7 @@ impl Monitor {
 			index,
 			name,
 			id,
+			focused,
 			workspaces,
 			rect: Rect {
 				left: monitor.size.left,
@@ -106,21 +128,28 @@ impl Monitor {
 	}
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct State {
 	pub monitors: Vec<Monitor>,
+	pub focused_monitor_idx: usize,
+	/// Colors mapped from the theme in komorebi's static config, if any.
+	pub theme: Option<ThemeColors>,
 }
 
 impl From<KState> for State {
 	fn from(state: KState) -> Self {
+		let focused_monitor_idx = state.monitors.focused_idx();
+
 		Self {
 			monitors: state
 				.monitors
 				.elements
 				.into_iter()
 				.enumerate()
-				.map(|(idx, monitor)| Monitor::from(monitor, idx))
+				.map(|(idx, monitor)| Monitor::from(monitor, idx, idx == focused_monitor_idx))
 				.collect(),
+			focused_monitor_idx,
+			theme: None,
 		}
 	}
 }
@@ -130,7 +159,68 @@ pub fn read_state() -> anyhow::Result<State> {
 
 	let response = client::send_query(KSocketMessage::State)?;
 	let state: KState = serde_json::from_str(&response)?;
-	Ok(state.into())
+
+	let mut state = State::from(state);
+	state.theme = read_theme();
+	Ok(state)
+}
+
+/// Gets the raw komorebi state as pretty printed JSON, from the last
+/// notification or by querying komorebi if none was received yet.
+#[cfg(windows)]
+pub fn read_raw_state() -> anyhow::Result<String> {
+	let state = match stats::last_notification() {
+		Some(notification) => {
+			let mut notification = serde_json::from_slice::<serde_json::Value>(&notification)?;
+			notification["state"].take()
+		}
+		None => serde_json::from_str(&client::send_query(KSocketMessage::State)?)?,
+	};
+
+	Ok(serde_json::to_string_pretty(&state)?)
+}
+
+/// Gets the file the last known state is cached to, used while komorebi
+/// isn't running.
+#[cfg(windows)]
+fn cached_state_file() -> anyhow::Result<PathBuf> {
+	let file = dirs::data_dir()
+		.context("Failed to get $data_dir path")?
+		.join("komorebi-switcher")
+		.join("state.json");
+	Ok(file)
+}
+
+/// Caches the state, to be restored by [`read_cached_state`] if komorebi
+/// isn't running on the next launch.
+#[cfg(windows)]
+pub fn save_cached_state(state: &State) -> anyhow::Result<()> {
+	let file = cached_state_file()?;
+	if let Some(parent) = file.parent() {
+		std::fs::create_dir_all(parent)?;
+	}
+
+	std::fs::write(file, serde_json::to_string(state)?)?;
+
+	Ok(())
+}
+
+/// Reads the last known state cached by [`save_cached_state`], if any.
+#[cfg(windows)]
+pub fn read_cached_state() -> Option<State> {
+	let file = cached_state_file().ok()?;
+	let contents = std::fs::read_to_string(file).ok()?;
+	serde_json::from_str(&contents)
+		.inspect_err(|e| tracing::warn!("Failed to parse cached komorebi state: {e}"))
+		.ok()
+}
+
+/// Reads the theme from komorebi's static config, logging errors if any.
+fn read_theme() -> Option<ThemeColors> {
+	theme::read_theme().unwrap_or_else(|e| {
+		tracing::warn!("Failed to read komorebi theme: {e:#}");
+		None
+	})
 }
 
 pub fn change_workspace(monitor_idx: usize, workspace_idx: usize) {
@@ -151,12 +241,125 @@ pub fn cycle_layout(direction: CycleDirection) {
 	}
 }
 
+/// Time to wait for komorebi to stop or start, see [`restart`].
+#[cfg(windows)]
+const CONTROL_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Set when komorebi is stopped from the switcher, to not warn about the
+/// disconnection.
+static STOP_REQUESTED: AtomicBool = AtomicBool::new(false);
+
+/// Whether komorebi is running and answering queries.
+#[cfg(windows)]
+fn is_running() -> bool {
+	client::send_query(KSocketMessage::State).is_ok()
+}
+
+/// Starts komorebi by running the given command line, and subscribes again
+/// once it is running so the switcher picks up its state.
+///
+/// Blocks until komorebi is running, or [`CONTROL_TIMEOUT`] elapsed.
+#[cfg(windows)]
+pub fn start(command: &str) -> anyhow::Result<()> {
+	tracing::info!("Starting komorebi with: {command}");
+
+	STOP_REQUESTED.store(false, Ordering::Relaxed);
+
+	let status = shell_command(command).status()?;
+	if !status.success() {
+		anyhow::bail!("`{command}` exited with {status}");
+	}
+
+	// The listener only notices komorebi stopped if it was told so,
+	// subscribing again is harmless otherwise
+	let connect_message = KSocketMessage::AddSubscriberSocket(SOCK_NAME.into());
+	let started_at = Instant::now();
+	while let Err(e) = client::send_message(&connect_message) {
+		if started_at.elapsed() > CONTROL_TIMEOUT {
+			anyhow::bail!("komorebi didn't start in time: {e}");
+		}
+		std::thread::sleep(Duration::from_millis(500));
+	}
+
+	Ok(())
+}
+
+/// Asks komorebi to stop, the switchers show the last known state until it
+/// is started again.
+#[cfg(windows)]
+pub fn stop() -> anyhow::Result<()> {
+	tracing::info!("Stopping komorebi");
+
+	STOP_REQUESTED.store(true, Ordering::Relaxed);
+	client::send_message(&KSocketMessage::Stop).inspect_err(|_| {
+		STOP_REQUESTED.store(false, Ordering::Relaxed);
+	})
+}
+
+/// Stops komorebi, waits for it to exit, and starts it again with the
+/// given command line.
+///
+/// Blocks until komorebi is running again, or [`CONTROL_TIMEOUT`] elapsed.
+#[cfg(windows)]
+pub fn restart(command: &str) -> anyhow::Result<()> {
+	if is_running() {
+		stop()?;
+
+		let stopped_at = Instant::now();
+		while is_running() {
+			if stopped_at.elapsed() > CONTROL_TIMEOUT {
+				anyhow::bail!("komorebi didn't stop in time");
+			}
+			std::thread::sleep(Duration::from_millis(500));
+		}
+	}
+
+	start(command)
+}
+
+/// Asks komorebi to reload its configuration.
+#[cfg(windows)]
+pub fn reload_configuration() -> anyhow::Result<()> {
+	tracing::info!("Reloading komorebi configuration");
+	client::send_message(&KSocketMessage::ReloadConfiguration)
+}
+
+/// Creates a command running the command line through the shell, without
+/// showing a console window.
+#[cfg(windows)]
+fn shell_command(command: &str) -> std::process::Command {
+	use std::os::windows::process::CommandExt;
+
+	const CREATE_NO_WINDOW: u32 = 0x08000000;
+
+	let mut cmd = std::process::Command::new("cmd");
+	cmd.arg("/C")
+		.raw_arg(command)
+		.creation_flags(CREATE_NO_WINDOW);
+	cmd
+}
+
 #[cfg(debug_assertions)]
 const SOCK_NAME: &str = "komorebi-switcher-debug.sock";
 #[cfg(not(debug_assertions))]
 const SOCK_NAME: &str = "komorebi-switcher.sock";
 
-pub fn listen_for_state(on_new_state: impl Fn(State) + Send + 'static) {
+/// Listens for komorebi notifications, calling `on_new_state` with the new
+/// state and `on_connection_changed` when the subscription is lost or
+/// established, which is followed by the current state.
+pub fn listen_for_state(
+	on_new_state: impl Fn(State) + Send + 'static,
+	on_connection_changed: impl Fn(bool) + Send + 'static,
+) {
+	// Gets the current state once connected, as komorebi only notifies changes
+	let on_connected = || {
+		on_connection_changed(true);
+		match read_state() {
+			Ok(state) => on_new_state(state),
+			Err(e) => tracing::error!("Failed to read komorebi state after connecting: {e}"),
+		}
+	};
+
 	let socket = loop {
 		match client::subscribe(SOCK_NAME) {
 			Ok(socket) => break socket,
@@ -165,6 +368,10 @@ pub fn listen_for_state(on_new_state: impl Fn(State) + Send + 'static) {
 	};
 
 	tracing::info!("Listenting for messages from komorebi");
+	stats::set_connected(true);
+	on_connected();
+
+	let mut theme = read_theme();
 
 	for client in socket.incoming() {
 		let client = match client {
@@ -186,6 +393,13 @@ pub fn listen_for_state(on_new_state: impl Fn(State) + Send + 'static) {
 		// this is when we know a shutdown has been sent
 		if matches!(reader.read_to_end(&mut buffer), Ok(0)) {
 			tracing::info!("Disconnected from komorebi");
+			stats::set_connected(false);
+			on_connection_changed(false);
+			if !STOP_REQUESTED.swap(false, Ordering::Relaxed) {
+				crate::notifications::warn(
+					"Disconnected from komorebi, reconnecting once it is running",
+				);
+			}
 
 			// keep trying to reconnect to komorebi
 			let connect_message = KSocketMessage::AddSubscriberSocket(SOCK_NAME.into());
@@ -195,38 +409,53 @@ pub fn listen_for_state(on_new_state: impl Fn(State) + Send + 'static) {
 			}
 
 			tracing::info!("Reconnected to komorebi");
+			stats::set_connected(true);
+			on_connected();
 
 			continue;
 		}
 
-		let value = match serde_json::from_slice::<serde_json::Value>(&buffer) {
-			Ok(value) => value,
-			Err(e) => {
-				tracing::error!("Failed to parse komorebi message: {e}");
-				continue;
-			}
-		};
-
-		tracing::trace!("Received komorebi message: {value}");
-
-		tracing::debug!(
-			"Received an event from komorebi: {}",
-			value
-				.get("event")
-				.and_then(|o| o.as_object())
-				.and_then(|o| o.get("type"))
-				.map(|v| v.to_string())
-				.unwrap_or_default()
+		tracing::trace!(
+			"Received komorebi message: {}",
+			String::from_utf8_lossy(&buffer)
 		);
 
-		let notification = match serde_json::from_value::<KNotification>(value) {
+		let notification = match serde_json::from_slice::<KNotification>(&buffer) {
 			Ok(notification) => notification,
 			Err(e) => {
 				tracing::error!("Failed to parse komorebi notification: {e}");
+				stats::record_parse_error();
 				continue;
 			}
 		};
 
-		on_new_state(notification.state.into());
+		let KNotificationEvent::Socket(event) = &notification.event;
+		tracing::debug!("Received an event from komorebi: {event}");
+		stats::record_event(event.to_string());
+
+		// Moved rather than cloned, only parsed again for diagnostics
+		stats::set_last_notification(buffer);
+
+		// Reload the theme when komorebi reloads its static config,
+		// keeping track of the path it was loaded from
+		match notification.event {
+			KNotificationEvent::Socket(
+				KSocketEvent::ReplaceConfiguration { content }
+				| KSocketEvent::ReloadStaticConfiguration { content },
+			) => {
+				if let Some(path) = content {
+					theme::set_static_config_path(path);
+				}
+				theme = read_theme();
+			}
+			KNotificationEvent::Socket(KSocketEvent::ReloadConfiguration) => {
+				theme = read_theme();
+			}
+			_ => {}
+		}
+
+		let mut state = State::from(notification.state);
+		state.theme = theme.clone();
+		on_new_state(state);
 	}
 }
@@ -0,0 +1,97 @@
+//! Queries about the running komorebi process.
+
+/// Gets the command line of the running komorebi process, if any.
+#[cfg(windows)]
+pub fn command_line() -> Option<String> {
+	use windows::Wdk::System::Threading::{NtQueryInformationProcess, PROCESSINFOCLASS};
+	use windows::Win32::Foundation::{CloseHandle, UNICODE_STRING};
+	use windows::Win32::System::Threading::{OpenProcess, PROCESS_QUERY_LIMITED_INFORMATION};
+
+	/// `ProcessCommandLineInformation`, which isn't defined by the windows crate.
+	const PROCESS_COMMAND_LINE_INFORMATION: PROCESSINFOCLASS = PROCESSINFOCLASS(60);
+
+	let pid = find_process("komorebi.exe")?;
+
+	unsafe {
+		let process = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid).ok()?;
+
+		// Query the required size first, then the command line
+		let mut len = 0;
+		let _ = NtQueryInformationProcess(
+			process,
+			PROCESS_COMMAND_LINE_INFORMATION,
+			std::ptr::null_mut(),
+			0,
+			&mut len,
+		);
+
+		// Aligned for the `UNICODE_STRING` header
+		let mut buffer = vec![0u64; (len as usize).div_ceil(8)];
+		let status = NtQueryInformationProcess(
+			process,
+			PROCESS_COMMAND_LINE_INFORMATION,
+			buffer.as_mut_ptr().cast(),
+			len,
+			&mut len,
+		);
+
+		let _ = CloseHandle(process);
+
+		if status.is_err() {
+			tracing::debug!("Failed to query komorebi command line: {status:?}");
+			return None;
+		}
+
+		let command_line = &*(buffer.as_ptr() as *const UNICODE_STRING);
+		if command_line.Buffer.is_null() {
+			return None;
+		}
+
+		let chars = std::slice::from_raw_parts(
+			command_line.Buffer.0,
+			command_line.Length as usize / size_of::<u16>(),
+		);
+		Some(String::from_utf16_lossy(chars))
+	}
+}
+
+#[cfg(not(windows))]
+pub fn command_line() -> Option<String> {
+	None
+}
+
+/// Finds the id of a running process by its executable name.
+#[cfg(windows)]
+fn find_process(name: &str) -> Option<u32> {
+	use windows::Win32::Foundation::CloseHandle;
+	use windows::Win32::System::Diagnostics::ToolHelp::{
+		CreateToolhelp32Snapshot, Process32FirstW, Process32NextW, PROCESSENTRY32W,
+		TH32CS_SNAPPROCESS,
+	};
+
+	unsafe {
+		let snapshot = CreateToolhelp32Snapshot(TH32CS_SNAPPROCESS, 0).ok()?;
+
+		let mut entry = PROCESSENTRY32W {
+			dwSize: size_of::<PROCESSENTRY32W>() as u32,
+			..Default::default()
+		};
+
+		let mut pid = None;
+		let mut next = Process32FirstW(snapshot, &mut entry);
+		while next.is_ok() {
+			let exe = &entry.szExeFile;
+			let len = exe.iter().position(|c| *c == 0).unwrap_or(exe.len());
+			if String::from_utf16_lossy(&exe[..len]).eq_ignore_ascii_case(name) {
+				pid = Some(entry.th32ProcessID);
+				break;
+			}
+
+			next = Process32NextW(snapshot, &mut entry);
+		}
+
+		let _ = CloseHandle(snapshot);
+
+		pid
+	}
+}
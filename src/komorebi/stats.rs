@@ -0,0 +1,92 @@
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Window over which the notification rate is measured.
+const RATE_WINDOW: Duration = Duration::from_secs(10);
+
+/// Diagnostics of the subscription to komorebi notifications.
+#[derive(Debug, Clone)]
+pub struct ListenerStats {
+	pub connected: bool,
+	/// Type of the last notification event, and when it was received.
+	pub last_event: Option<(String, Instant)>,
+	pub notifications: u64,
+	/// Messages that couldn't be parsed as JSON or as a notification.
+	pub parse_errors: u64,
+	/// Times at which the notifications in the last [`RATE_WINDOW`] were received.
+	recent: VecDeque<Instant>,
+}
+
+impl ListenerStats {
+	/// Notifications received per second over the last [`RATE_WINDOW`].
+	#[cfg(windows)]
+	pub fn notification_rate(&self) -> f32 {
+		let now = Instant::now();
+		let count = self
+			.recent
+			.iter()
+			.filter(|at| now - **at <= RATE_WINDOW)
+			.count();
+		count as f32 / RATE_WINDOW.as_secs_f32()
+	}
+}
+
+static STATS: Mutex<ListenerStats> = Mutex::new(ListenerStats {
+	connected: false,
+	last_event: None,
+	notifications: 0,
+	parse_errors: 0,
+	recent: VecDeque::new(),
+});
+
+/// The last notification as received, kept apart from [`STATS`] as it is
+/// only parsed when exporting diagnostics.
+static LAST_NOTIFICATION: Mutex<Option<Vec<u8>>> = Mutex::new(None);
+
+/// Gets a snapshot of the listener diagnostics.
+#[cfg(windows)]
+pub fn listener_stats() -> ListenerStats {
+	STATS.lock().unwrap().clone()
+}
+
+/// Gets the type of the last notification event and when it was received,
+/// without blocking as it is called from the panic hook.
+pub fn last_event() -> Option<(String, Instant)> {
+	STATS.try_lock().ok()?.last_event.clone()
+}
+
+/// Gets the last notification received from komorebi, as received.
+#[cfg(windows)]
+pub fn last_notification() -> Option<Vec<u8>> {
+	LAST_NOTIFICATION.lock().unwrap().clone()
+}
+
+pub(super) fn set_last_notification(notification: Vec<u8>) {
+	*LAST_NOTIFICATION.lock().unwrap() = Some(notification);
+}
+
+pub(super) fn set_connected(connected: bool) {
+	STATS.lock().unwrap().connected = connected;
+}
+
+pub(super) fn record_parse_error() {
+	STATS.lock().unwrap().parse_errors += 1;
+}
+
+pub(super) fn record_event(event: String) {
+	let now = Instant::now();
+
+	let mut stats = STATS.lock().unwrap();
+	stats.last_event = Some((event, now));
+	stats.notifications += 1;
+
+	stats.recent.push_back(now);
+	while stats
+		.recent
+		.front()
+		.is_some_and(|at| now - *at > RATE_WINDOW)
+	{
+		stats.recent.pop_front();
+	}
+}
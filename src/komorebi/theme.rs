@@ -0,0 +1,412 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use anyhow::Context;
+use serde::Deserialize;
+
+use crate::theme::ThemeColors;
+
+/// Subset of komorebi's static config (`komorebi.json`) we care about.
+#[derive(Debug, Deserialize)]
+struct KStaticConfig {
+	theme: Option<KTheme>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "palette")]
+enum KTheme {
+	Catppuccin {
+		name: String,
+		bar_accent: Option<String>,
+	},
+	Base16 {
+		name: String,
+		bar_accent: Option<String>,
+	},
+	Custom {
+		colours: HashMap<String, serde_json::Value>,
+		bar_accent: Option<String>,
+	},
+}
+
+impl KTheme {
+	/// Maps the theme palette to the switcher colors, the active indicator
+	/// uses the komorebi-bar accent color, like komorebi-bar does.
+	fn theme_colors(&self) -> anyhow::Result<ThemeColors> {
+		match self {
+			KTheme::Catppuccin { name, bar_accent } => {
+				let palette = catppuccin_palette(name)
+					.with_context(|| format!("Unknown Catppuccin flavor `{name}`"))?;
+				let color = |name: &str| {
+					let idx = CATPPUCCIN_COLORS.iter().position(|c| *c == name)?;
+					Some(palette[idx].to_string())
+				};
+
+				Ok(ThemeColors {
+					text: color("Text"),
+					inactive_text: color("Subtext0"),
+					empty_text: color("Overlay0"),
+					active_indicator: color(bar_accent.as_deref().unwrap_or("Blue")),
+					busy_indicator: color("Overlay1"),
+					..Default::default()
+				})
+			}
+			KTheme::Base16 { name, bar_accent } => {
+				let palette = base16_palette(name).with_context(|| {
+					format!("Unknown Base16 palette `{name}`, use a Custom palette instead")
+				})?;
+				let color = |name: &str| {
+					let idx = BASE16_COLORS.iter().position(|c| *c == name)?;
+					Some(palette[idx].to_string())
+				};
+
+				Ok(base16_theme_colors(color, bar_accent.as_deref()))
+			}
+			KTheme::Custom {
+				colours,
+				bar_accent,
+			} => {
+				// Palette values are `Base0D` while the keys are `base_0d`
+				let color = |name: &str| {
+					let key = name.to_ascii_lowercase().replace("base", "base_");
+					base16_color(colours.get(&key)?)
+				};
+
+				Ok(base16_theme_colors(color, bar_accent.as_deref()))
+			}
+		}
+	}
+}
+
+/// Maps Base16 palette colors to the switcher colors.
+fn base16_theme_colors(
+	color: impl Fn(&str) -> Option<String>,
+	bar_accent: Option<&str>,
+) -> ThemeColors {
+	ThemeColors {
+		text: color("Base05"),
+		inactive_text: color("Base04"),
+		empty_text: color("Base03"),
+		active_indicator: color(bar_accent.unwrap_or("Base0D")),
+		busy_indicator: color("Base03"),
+		..Default::default()
+	}
+}
+
+/// Reads komorebi-switcher colors from the theme in komorebi's static config,
+/// returns `None` if komorebi has no theme configured.
+pub fn read_theme() -> anyhow::Result<Option<ThemeColors>> {
+	let path = static_config_path()?;
+	if !path.exists() {
+		tracing::debug!("komorebi static config not found at {}", path.display());
+		return Ok(None);
+	}
+
+	tracing::info!("Reading komorebi theme from {}", path.display());
+
+	let content = std::fs::read_to_string(&path)
+		.with_context(|| format!("Failed to read {}", path.display()))?;
+	let config: KStaticConfig = serde_json::from_str(&content)
+		.with_context(|| format!("Failed to parse {}", path.display()))?;
+
+	config.theme.map(|theme| theme.theme_colors()).transpose()
+}
+
+/// Path of the static config komorebi last reported loading,
+/// see [`set_static_config_path`].
+static REPORTED_PATH: Mutex<Option<PathBuf>> = Mutex::new(None);
+
+/// Sets the path of the static config komorebi reported loading,
+/// i.e when its configuration is replaced.
+pub fn set_static_config_path(path: PathBuf) {
+	*REPORTED_PATH.lock().unwrap() = Some(path);
+}
+
+/// Gets the path of komorebi's static config, in order:
+/// - the path komorebi last reported loading
+/// - the `--config` argument of the running komorebi process
+/// - `komorebi.json` in `$KOMOREBI_CONFIG_HOME` if set, or the home directory
+fn static_config_path() -> anyhow::Result<PathBuf> {
+	if let Some(path) = REPORTED_PATH.lock().unwrap().clone() {
+		return Ok(path);
+	}
+
+	if let Some(path) = super::process::command_line().and_then(|cmd| config_arg(&cmd)) {
+		return Ok(path);
+	}
+
+	let dir = match std::env::var_os("KOMOREBI_CONFIG_HOME") {
+		Some(dir) => PathBuf::from(dir),
+		None => dirs::home_dir().context("Could not determine home directory")?,
+	};
+
+	Ok(dir.join("komorebi.json"))
+}
+
+/// Gets the value of the `--config` (or `-c`) argument of a komorebi
+/// command line, arguments with spaces are expected to be quoted.
+fn config_arg(command_line: &str) -> Option<PathBuf> {
+	let mut args = split_command_line(command_line).into_iter();
+
+	while let Some(arg) = args.next() {
+		if arg == "--config" || arg == "-c" {
+			return args.next().map(PathBuf::from);
+		}
+
+		if let Some(path) = arg.strip_prefix("--config=") {
+			return Some(PathBuf::from(path));
+		}
+	}
+
+	None
+}
+
+/// Splits a command line on whitespace, keeping quoted arguments together.
+fn split_command_line(command_line: &str) -> Vec<String> {
+	let mut args = Vec::new();
+	let mut arg = String::new();
+	let mut quoted = false;
+	let mut in_arg = false;
+
+	for c in command_line.chars() {
+		match c {
+			'"' => {
+				quoted = !quoted;
+				in_arg = true;
+			}
+			c if c.is_whitespace() && !quoted => {
+				if in_arg {
+					args.push(std::mem::take(&mut arg));
+					in_arg = false;
+				}
+			}
+			c => {
+				arg.push(c);
+				in_arg = true;
+			}
+		}
+	}
+
+	if in_arg {
+		args.push(arg);
+	}
+
+	args
+}
+
+/// Converts a custom palette color, either a hex string or an `{ r, g, b }` object.
+fn base16_color(value: &serde_json::Value) -> Option<String> {
+	if let Some(hex) = value.as_str() {
+		return Some(hex.to_string());
+	}
+
+	let channel = |c: &str| value.get(c)?.as_u64();
+	let (r, g, b) = (channel("r")?, channel("g")?, channel("b")?);
+	Some(format!("rgb({r}, {g}, {b})"))
+}
+
+const BASE16_COLORS: [&str; 16] = [
+	"Base00", "Base01", "Base02", "Base03", "Base04", "Base05", "Base06", "Base07", "Base08",
+	"Base09", "Base0A", "Base0B", "Base0C", "Base0D", "Base0E", "Base0F",
+];
+
+/// Gets the colors of a named Base16 palette, in the order of [`BASE16_COLORS`].
+///
+/// Names are matched ignoring case and separators, so `TomorrowNight`
+/// and `tomorrow-night` are the same palette.
+fn base16_palette(name: &str) -> Option<[&'static str; 16]> {
+	let name: String = name
+		.chars()
+		.filter(char::is_ascii_alphanumeric)
+		.map(|c| c.to_ascii_lowercase())
+		.collect();
+
+	let palette = match name.as_str() {
+		"ashes" => [
+			"#1C2023", "#393F45", "#565E65", "#747C84", "#ADB3BA", "#C7CCD1", "#DFE2E5", "#F3F4F5",
+			"#C7AE95", "#C7C795", "#AEC795", "#95C7AE", "#95AEC7", "#AE95C7", "#C795AE", "#C79595",
+		],
+		"defaultdark" => [
+			"#181818", "#282828", "#383838", "#585858", "#B8B8B8", "#D8D8D8", "#E8E8E8", "#F8F8F8",
+			"#AB4642", "#DC9656", "#F7CA88", "#A1B56C", "#86C1B9", "#7CAFC2", "#BA8BAF", "#A16946",
+		],
+		"defaultlight" => [
+			"#F8F8F8", "#E8E8E8", "#D8D8D8", "#B8B8B8", "#585858", "#383838", "#282828", "#181818",
+			"#AB4642", "#DC9656", "#F7CA88", "#A1B56C", "#86C1B9", "#7CAFC2", "#BA8BAF", "#A16946",
+		],
+		"eighties" => [
+			"#2D2D2D", "#393939", "#515151", "#747369", "#A09F93", "#D3D0C8", "#E8E6DF", "#F2F0EC",
+			"#F2777A", "#F99157", "#FFCC66", "#99CC99", "#66CCCC", "#6699CC", "#CC99CC", "#D27B53",
+		],
+		"gruvboxdarkhard" => [
+			"#1D2021", "#3C3836", "#504945", "#665C54", "#BDAE93", "#D5C4A1", "#EBDBB2", "#FBF1C7",
+			"#FB4934", "#FE8019", "#FABD2F", "#B8BB26", "#8EC07C", "#83A598", "#D3869B", "#D65D0E",
+		],
+		"gruvboxdarkmedium" => [
+			"#282828", "#3C3836", "#504945", "#665C54", "#BDAE93", "#D5C4A1", "#EBDBB2", "#FBF1C7",
+			"#FB4934", "#FE8019", "#FABD2F", "#B8BB26", "#8EC07C", "#83A598", "#D3869B", "#D65D0E",
+		],
+		"gruvboxdarksoft" => [
+			"#32302F", "#3C3836", "#504945", "#665C54", "#BDAE93", "#D5C4A1", "#EBDBB2", "#FBF1C7",
+			"#FB4934", "#FE8019", "#FABD2F", "#B8BB26", "#8EC07C", "#83A598", "#D3869B", "#D65D0E",
+		],
+		"mocha" => [
+			"#3B3228", "#534636", "#645240", "#7E705A", "#B8AFAD", "#D0C8C6", "#E9E1DD", "#F5EEEB",
+			"#CB6077", "#D28B71", "#F4BC87", "#BEB55B", "#7BBDA4", "#8AB3B5", "#A89BB9", "#BB9584",
+		],
+		"monokai" => [
+			"#272822", "#383830", "#49483E", "#75715E", "#A59F85", "#F8F8F2", "#F5F4F1", "#F9F8F5",
+			"#F92672", "#FD971F", "#F4BF75", "#A6E22E", "#A1EFE4", "#66D9EF", "#AE81FF", "#CC6633",
+		],
+		"nord" => [
+			"#2E3440", "#3B4252", "#434C5E", "#4C566A", "#D8DEE9", "#E5E9F0", "#ECEFF4", "#8FBCBB",
+			"#BF616A", "#D08770", "#EBCB8B", "#A3BE8C", "#88C0D0", "#81A1C1", "#B48EAD", "#5E81AC",
+		],
+		"ocean" => [
+			"#2B303B", "#343D46", "#4F5B66", "#65737E", "#A7ADBA", "#C0C5CE", "#DFE1E8", "#EFF1F5",
+			"#BF616A", "#D08770", "#EBCB8B", "#A3BE8C", "#96B5B4", "#8FA1B3", "#B48EAD", "#AB7967",
+		],
+		"onedark" => [
+			"#282C34", "#353B45", "#3E4451", "#545862", "#565C64", "#ABB2BF", "#B6BDCA", "#C8CCD4",
+			"#E06C75", "#D19A66", "#E5C07B", "#98C379", "#56B6C2", "#61AFEF", "#C678DD", "#BE5046",
+		],
+		"solarizeddark" => [
+			"#002B36", "#073642", "#586E75", "#657B83", "#839496", "#93A1A1", "#EEE8D5", "#FDF6E3",
+			"#DC322F", "#CB4B16", "#B58900", "#859900", "#2AA198", "#268BD2", "#6C71C4", "#D33682",
+		],
+		"solarizedlight" => [
+			"#FDF6E3", "#EEE8D5", "#93A1A1", "#839496", "#657B83", "#586E75", "#073642", "#002B36",
+			"#DC322F", "#CB4B16", "#B58900", "#859900", "#2AA198", "#268BD2", "#6C71C4", "#D33682",
+		],
+		"tomorrow" => [
+			"#FFFFFF", "#E0E0E0", "#D6D6D6", "#8E908C", "#969896", "#4D4D4C", "#282A2E", "#1D1F21",
+			"#C82829", "#F5871F", "#EAB700", "#718C00", "#3E999F", "#4271AE", "#8959A8", "#A3685A",
+		],
+		"tomorrownight" => [
+			"#1D1F21", "#282A2E", "#373B41", "#969896", "#B4B7B4", "#C5C8C6", "#E0E0E0", "#FFFFFF",
+			"#CC6666", "#DE935F", "#F0C674", "#B5BD68", "#8ABEB7", "#81A2BE", "#B294BB", "#A3685A",
+		],
+		_ => return None,
+	};
+
+	Some(palette)
+}
+
+const CATPPUCCIN_COLORS: [&str; 26] = [
+	"Rosewater",
+	"Flamingo",
+	"Pink",
+	"Mauve",
+	"Red",
+	"Maroon",
+	"Peach",
+	"Yellow",
+	"Green",
+	"Teal",
+	"Sky",
+	"Sapphire",
+	"Blue",
+	"Lavender",
+	"Text",
+	"Subtext1",
+	"Subtext0",
+	"Overlay2",
+	"Overlay1",
+	"Overlay0",
+	"Surface2",
+	"Surface1",
+	"Surface0",
+	"Base",
+	"Mantle",
+	"Crust",
+];
+
+/// Gets the colors of a Catppuccin flavor, in the order of [`CATPPUCCIN_COLORS`].
+fn catppuccin_palette(flavor: &str) -> Option<[&'static str; 26]> {
+	let palette = match flavor {
+		"Latte" => [
+			"#DC8A78", "#DD7878", "#EA76CB", "#8839EF", "#D20F39", "#E64553", "#FE640B", "#DF8E1D",
+			"#40A02B", "#179299", "#04A5E5", "#209FB5", "#1E66F5", "#7287FD", "#4C4F69", "#5C5F77",
+			"#6C6F85", "#7C7F93", "#8C8FA1", "#9CA0B0", "#ACB0BE", "#BCC0CC", "#CCD0DA", "#EFF1F5",
+			"#E6E9EF", "#DCE0E8",
+		],
+		"Frappe" => [
+			"#F2D5CF", "#EEBEBE", "#F4B8E4", "#CA9EE6", "#E78284", "#EA999C", "#EF9F76", "#E5C890",
+			"#A6D189", "#81C8BE", "#99D1DB", "#85C1DC", "#8CAAEE", "#BABBF1", "#C6D0F5", "#B5BFE2",
+			"#A5ADCE", "#949CBB", "#838BA7", "#737994", "#626880", "#51576D", "#414559", "#303446",
+			"#292C3C", "#232634",
+		],
+		"Macchiato" => [
+			"#F4DBD6", "#F0C6C6", "#F5BDE6", "#C6A0F6", "#ED8796", "#EE99A0", "#F5A97F", "#EED49F",
+			"#A6DA95", "#8BD5CA", "#91D7E3", "#7DC4E4", "#8AADF4", "#B7BDF8", "#CAD3F5", "#B8C0E0",
+			"#A5ADCB", "#939AB7", "#8087A2", "#6E738D", "#5B6078", "#494D64", "#363A4F", "#24273A",
+			"#1E2030", "#181926",
+		],
+		"Mocha" => [
+			"#F5E0DC", "#F2CDCD", "#F5C2E7", "#CBA6F7", "#F38BA8", "#EBA0AC", "#FAB387", "#F9E2AF",
+			"#A6E3A1", "#94E2D5", "#89DCEB", "#74C7EC", "#89B4FA", "#B4BEFE", "#CDD6F4", "#BAC2DE",
+			"#A6ADC8", "#9399B2", "#7F849C", "#6C7086", "#585B70", "#45475A", "#313244", "#1E1E2E",
+			"#181825", "#11111B",
+		],
+		_ => return None,
+	};
+
+	Some(palette)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn config_arg_from_command_line() {
+		let path = |cmd: &str| config_arg(cmd).map(|p| p.display().to_string());
+
+		assert_eq!(
+			path(
+				r#""C:\Program Files\komorebi\bin\komorebi.exe" --config "C:\Users\me\my config.json""#
+			),
+			Some(r"C:\Users\me\my config.json".to_string())
+		);
+		assert_eq!(
+			path(r"komorebi.exe -c C:\komorebi.json --bar"),
+			Some(r"C:\komorebi.json".to_string())
+		);
+		assert_eq!(
+			path(r"komorebi.exe --config=C:\komorebi.json"),
+			Some(r"C:\komorebi.json".to_string())
+		);
+		assert_eq!(path("komorebi.exe --config"), None);
+		assert_eq!(path("komorebi.exe --await-configuration"), None);
+	}
+
+	#[test]
+	fn base16_palette_names() {
+		assert!(base16_palette("TomorrowNight").is_some());
+		assert_eq!(
+			base16_palette("tomorrow-night"),
+			base16_palette("TomorrowNight")
+		);
+		assert_eq!(
+			base16_palette("Gruvbox Dark Hard"),
+			base16_palette("GruvboxDarkHard")
+		);
+		assert_eq!(base16_palette("NotAPalette"), None);
+	}
+
+	#[test]
+	fn base16_theme() {
+		let theme: KTheme = serde_json::from_str(
+			r#"{ "palette": "Base16", "name": "Ocean", "bar_accent": "Base0E" }"#,
+		)
+		.unwrap();
+		let colors = theme.theme_colors().unwrap();
+		assert_eq!(colors.text.as_deref(), Some("#C0C5CE"));
+		assert_eq!(colors.active_indicator.as_deref(), Some("#B48EAD"));
+
+		let theme: KTheme =
+			serde_json::from_str(r#"{ "palette": "Base16", "name": "Unknown" }"#).unwrap();
+		assert!(theme.theme_colors().is_err());
+	}
+}
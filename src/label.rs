@@ -0,0 +1,154 @@
+use crate::komorebi::Workspace;
+
+/// Default label format, shows the workspace name.
+pub const DEFAULT_LABEL_FORMAT: &str = "{name}";
+
+/// Formats a workspace label from the given template.
+///
+/// Supported placeholders:
+/// - `{index}`: 1-based workspace index
+/// - `{name}`: workspace name, or index if unnamed
+/// - `{windows}`: number of windows in the workspace
+/// - `{layout}`: workspace layout
+/// - `{app}`: executable name of the focused window, empty if none
+/// - `{icon}`: workspace icon, or name if no icon is set
+///
+/// Use `{{` and `}}` to escape braces, unknown placeholders are kept as is.
+/// Falls back to the workspace name if the label ends up empty.
+pub fn format_label(format: &str, workspace: &Workspace, icon: Option<&str>) -> String {
+	let mut label = String::with_capacity(format.len());
+	let mut rest = format;
+
+	while let Some(idx) = rest.find(['{', '}']) {
+		label.push_str(&rest[..idx]);
+		rest = &rest[idx..];
+
+		// Escaped braces
+		if rest.starts_with("{{") || rest.starts_with("}}") {
+			label.push_str(&rest[..1]);
+			rest = &rest[2..];
+			continue;
+		}
+
+		// Stray closing brace or unclosed placeholder
+		let end = match rest.find('}') {
+			Some(end) if rest.starts_with('{') => end,
+			_ => {
+				label.push_str(&rest[..1]);
+				rest = &rest[1..];
+				continue;
+			}
+		};
+
+		let key = &rest[1..end];
+		match key {
+			"index" => label.push_str(&(workspace.index + 1).to_string()),
+			"name" => label.push_str(&workspace.name),
+			"windows" => label.push_str(&workspace.window_count.to_string()),
+			"layout" => label.push_str(&workspace.layout),
+			"app" => label.push_str(workspace.focused_app.as_deref().unwrap_or_default()),
+			"icon" => label.push_str(icon.unwrap_or(&workspace.name)),
+			_ => label.push_str(&rest[..=end]),
+		}
+
+		rest = &rest[end + 1..];
+	}
+
+	label.push_str(rest);
+
+	let label = label.trim();
+	if label.is_empty() {
+		workspace.name.clone()
+	} else {
+		label.to_string()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn workspace() -> Workspace {
+		Workspace {
+			name: "web".to_string(),
+			index: 1,
+			focused: true,
+			is_empty: false,
+			layout: "BSP".to_string(),
+			window_count: 3,
+			focused_app: Some("firefox.exe".to_string()),
+		}
+	}
+
+	#[test]
+	fn placeholders() {
+		let ws = workspace();
+		assert_eq!(format_label("{index}", &ws, None), "2");
+		assert_eq!(format_label("{name}", &ws, None), "web");
+		assert_eq!(format_label("{windows}", &ws, None), "3");
+		assert_eq!(format_label("{layout}", &ws, None), "BSP");
+		assert_eq!(format_label("{app}", &ws, None), "firefox.exe");
+		assert_eq!(format_label("{icon}", &ws, Some("W")), "W");
+		assert_eq!(format_label("{icon} {name}", &ws, Some("W")), "W web");
+		assert_eq!(
+			format_label("{index}: {name} ({windows})", &ws, None),
+			"2: web (3)"
+		);
+	}
+
+	#[test]
+	fn icon_falls_back_to_name() {
+		assert_eq!(format_label("{icon}", &workspace(), None), "web");
+	}
+
+	#[test]
+	fn escaped_braces() {
+		let ws = workspace();
+		assert_eq!(format_label("{{name}}", &ws, None), "{name}");
+		assert_eq!(format_label("{{{name}}}", &ws, None), "{web}");
+		assert_eq!(format_label("a }} b", &ws, None), "a } b");
+	}
+
+	#[test]
+	fn unknown_placeholders_are_kept() {
+		let ws = workspace();
+		assert_eq!(format_label("{foo}", &ws, None), "{foo}");
+		assert_eq!(format_label("{foo} {name}", &ws, None), "{foo} web");
+		assert_eq!(format_label("{}", &ws, None), "{}");
+	}
+
+	#[test]
+	fn unterminated_placeholder() {
+		let ws = workspace();
+		assert_eq!(format_label("{name", &ws, None), "{name");
+		assert_eq!(format_label("{name} {", &ws, None), "web {");
+		assert_eq!(format_label("a } b", &ws, None), "a } b");
+	}
+
+	#[test]
+	fn empty_workspace() {
+		let ws = Workspace {
+			is_empty: true,
+			window_count: 0,
+			focused_app: None,
+			..workspace()
+		};
+		assert_eq!(format_label("{windows}", &ws, None), "0");
+		// Falls back to the name when the label ends up empty
+		assert_eq!(format_label("{app}", &ws, None), "web");
+		assert_eq!(format_label("  ", &ws, None), "web");
+	}
+
+	#[test]
+	fn unnamed_workspace() {
+		// komorebi's unnamed workspaces are named after their 1-based index
+		let ws = Workspace {
+			name: "3".to_string(),
+			index: 2,
+			..workspace()
+		};
+		assert_eq!(format_label("{name}", &ws, None), "3");
+		assert_eq!(format_label("{icon}", &ws, None), "3");
+		assert_eq!(format_label("", &ws, None), "3");
+	}
+}
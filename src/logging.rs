@@ -0,0 +1,222 @@
+use std::collections::VecDeque;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, SystemTime};
+
+use tracing_appender::non_blocking::WorkerGuard;
+use tracing_subscriber::layer::{Layered, SubscriberExt};
+use tracing_subscriber::{reload, EnvFilter, Layer, Registry};
+
+use crate::config::{LogFormat, LoggingConfig};
+
+const ENV_VAR: &str = "KOMOREBI_SWITCHER_LOG";
+
+#[cfg(debug_assertions)]
+const FILE_NAME: &str = "komorebi-switcher-debug.log";
+#[cfg(not(debug_assertions))]
+const FILE_NAME: &str = "komorebi-switcher.log";
+
+/// Number of log lines kept in memory for crash reports.
+const RECENT_LINES: usize = 100;
+
+/// Handle to change the level filter at runtime, see [`set_level`]. Only set
+/// when the filter comes from the config.
+static FILTER: OnceLock<reload::Handle<EnvFilter, Registry>> = OnceLock::new();
+
+/// Directory the log files are written to, if file logging could be set up.
+static LOGS_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+/// The last [`RECENT_LINES`] log lines, see [`recent_lines`].
+static RECENT: Mutex<VecDeque<String>> = Mutex::new(VecDeque::new());
+
+/// Initializes logging to stdout and to daily log files, returning a guard
+/// that flushes the log files when dropped.
+pub fn init(config: &LoggingConfig) -> anyhow::Result<Option<WorkerGuard>> {
+	let env_filter = match std::env::var(ENV_VAR) {
+		Ok(env) => Some(parse_filter(&env)?),
+		Err(_) => EnvFilter::try_from_default_env().ok(),
+	};
+	let from_env = env_filter.is_some();
+
+	let (filter, filter_error) = match env_filter {
+		Some(filter) => (Ok(filter), None),
+		None => match parse_filter(&config.level) {
+			Ok(filter) => (Ok(filter), None),
+			Err(e) => (parse_filter(&LoggingConfig::default().level), Some(e)),
+		},
+	};
+
+	let (filter, handle) = reload::Layer::new(filter?);
+
+	let stdout_layer = tracing_subscriber::fmt::layer()
+		.compact()
+		.with_target(false);
+
+	let recent_layer = tracing_subscriber::fmt::layer()
+		.with_ansi(false)
+		.with_writer(|| RecentWriter);
+
+	let (file_layer, guard, file_error) = match file_layer(config) {
+		Ok((layer, guard)) => (Some(layer), Some(guard), None),
+		Err(e) => (None, None, Some(e)),
+	};
+
+	let subscriber = tracing_subscriber::registry()
+		.with(filter)
+		.with(file_layer)
+		.with(stdout_layer)
+		.with(recent_layer);
+
+	tracing::subscriber::set_global_default(subscriber)?;
+
+	// Keep the filter from the environment for the whole run
+	if !from_env {
+		let _ = FILTER.set(handle);
+	}
+
+	tracing::debug!("Initialized Logger");
+
+	if let Some(e) = filter_error {
+		tracing::warn!("Invalid `logging.level` value {:?}: {e}", config.level);
+	}
+	if let Some(e) = file_error {
+		tracing::error!("Failed to set up log files: {e:#}");
+	}
+
+	Ok(guard)
+}
+
+/// Changes the level filter, unless it was set by the `KOMOREBI_SWITCHER_LOG`
+/// or `RUST_LOG` environment variables.
+#[cfg(windows)]
+pub fn set_level(level: &str) {
+	let Some(handle) = FILTER.get() else {
+		return;
+	};
+
+	let filter = match parse_filter(level) {
+		Ok(filter) => filter,
+		Err(e) => {
+			tracing::warn!("Invalid `logging.level` value {level:?}: {e}");
+			return;
+		}
+	};
+
+	match handle.reload(filter) {
+		Ok(()) => tracing::info!("Changed log level to {level:?}"),
+		Err(e) => tracing::error!("Failed to change log level: {e}"),
+	}
+}
+
+/// Gets the directory the log files are written to.
+#[cfg(windows)]
+pub fn logs_dir() -> Option<&'static Path> {
+	LOGS_DIR.get().map(PathBuf::as_path)
+}
+
+/// Whether the file in the logs directory is one of our log files, as the
+/// directory may be shared with other apps.
+pub fn is_log_file(name: &str) -> bool {
+	name.starts_with(FILE_NAME)
+}
+
+/// Gets the last log lines without blocking, as it is called from the panic hook.
+pub fn recent_lines() -> Vec<String> {
+	match RECENT.try_lock() {
+		Ok(recent) => recent.iter().cloned().collect(),
+		Err(_) => Vec::new(),
+	}
+}
+
+/// Keeps the log lines in [`RECENT`], each event is written at once.
+struct RecentWriter;
+
+impl Write for RecentWriter {
+	fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+		let line = String::from_utf8_lossy(buf).trim_end().to_string();
+
+		if let Ok(mut recent) = RECENT.lock() {
+			recent.push_back(line);
+			while recent.len() > RECENT_LINES {
+				recent.pop_front();
+			}
+		}
+
+		Ok(buf.len())
+	}
+
+	fn flush(&mut self) -> std::io::Result<()> {
+		Ok(())
+	}
+}
+
+fn parse_filter(filter: &str) -> anyhow::Result<EnvFilter> {
+	Ok(EnvFilter::builder().parse(filter)?)
+}
+
+type FilteredRegistry = Layered<reload::Layer<EnvFilter, Registry>, Registry>;
+type BoxedLayer = Box<dyn Layer<FilteredRegistry> + Send + Sync>;
+
+fn file_layer(config: &LoggingConfig) -> anyhow::Result<(BoxedLayer, WorkerGuard)> {
+	let logs_dir = config.directory()?;
+
+	remove_old_logs(&logs_dir, config);
+
+	let appender = tracing_appender::rolling::daily(&logs_dir, FILE_NAME);
+	let (non_blocking, guard) = tracing_appender::non_blocking(appender);
+
+	let layer = tracing_subscriber::fmt::layer()
+		// disable ansi coloring in log file
+		.with_ansi(false)
+		.with_writer(non_blocking);
+
+	let layer = match config.format {
+		LogFormat::Plain => layer.boxed(),
+		LogFormat::Json => layer.json().boxed(),
+	};
+
+	let _ = LOGS_DIR.set(logs_dir);
+
+	Ok((layer, guard))
+}
+
+/// Removes the log files older than the retention period, then the oldest
+/// ones until they fit in the maximum size.
+fn remove_old_logs(logs_dir: &Path, config: &LoggingConfig) {
+	let retention = Duration::from_secs(60 * 60 * 24 * config.retention_days as u64);
+	let max_size = config.max_size * 1024 * 1024;
+	let now = SystemTime::now();
+
+	// Only our own files, as the directory may be shared with other apps
+	let mut logs = std::fs::read_dir(logs_dir)
+		.into_iter()
+		.flatten()
+		.filter_map(|entry| {
+			let entry = entry.ok()?;
+			if !is_log_file(&entry.file_name().to_string_lossy()) {
+				return None;
+			}
+
+			let metadata = entry.metadata().ok()?;
+			let modified = metadata.modified().ok()?;
+			Some((entry.path(), modified, metadata.len()))
+		})
+		.collect::<Vec<_>>();
+
+	// Newest first
+	logs.sort_by_key(|(_, modified, _)| std::cmp::Reverse(*modified));
+
+	let mut total_size = 0;
+	for (path, modified, size) in logs {
+		total_size += size;
+
+		let elapsed = now.duration_since(modified).unwrap_or_default();
+		let expired = config.retention_days > 0 && elapsed > retention;
+		let too_large = config.max_size > 0 && total_size > max_size;
+
+		if expired || too_large {
+			let _ = std::fs::remove_file(path);
+		}
+	}
+}
@@ -1,16 +1,22 @@
-use std::cell::{Cell, RefCell};
+use std::cell::{Cell, OnceCell, RefCell};
 
 use objc2::rc::Retained;
 use objc2::{define_class, msg_send, sel, AnyThread, DefinedClass, MainThreadOnly};
 use objc2_app_kit::{NSButton, NSColor, NSEvent, NSFont, NSTrackingArea, NSTrackingAreaOptions};
 use objc2_foundation::{MainThreadMarker, NSObjectProtocol, NSString};
 
+use crate::color_expr::SystemColors;
+use crate::config::MetricsConfig;
 use crate::komorebi::CycleDirection;
+use crate::macos::utils::ns_color_from_color;
+use crate::macos::workspace_button::{min_size, WorkspaceButton};
+use crate::theme::ThemeColors;
 
 #[derive(Debug)]
 pub struct LayoutButtonIvars {
 	workspace: crate::komorebi::Workspace,
 	is_hovering: Cell<bool>,
+	hover_background_color: OnceCell<Retained<NSColor>>,
 	tracking_area: RefCell<Option<Retained<NSTrackingArea>>>,
 }
 
@@ -19,6 +25,7 @@ impl LayoutButtonIvars {
 		Self {
 			workspace,
 			is_hovering: Cell::new(false),
+			hover_background_color: OnceCell::new(),
 			tracking_area: RefCell::new(None),
 		}
 	}
@@ -45,7 +52,10 @@ define_class!(
 		fn mouse_entered(&self, _event: &NSEvent) {
 			self.ivars().is_hovering.set(true);
 			let layer = self.layer().unwrap();
-			let hover_color = NSColor::colorWithWhite_alpha(1.0, 0.1).CGColor();
+			let hover_color = match self.ivars().hover_background_color.get() {
+				Some(color) => color.CGColor(),
+				None => NSColor::colorWithWhite_alpha(1.0, 0.1).CGColor(),
+			};
 			let _: () = unsafe { msg_send![&layer, setBackgroundColor: &*hover_color] };
 		}
 
@@ -93,6 +103,9 @@ impl LayoutButton {
 		mtm: MainThreadMarker,
 		workspace: &crate::komorebi::Workspace,
 		font: Option<&NSFont>,
+		metrics: &MetricsConfig,
+		colors: &ThemeColors,
+		system_colors: &SystemColors,
 	) -> Retained<Self> {
 		// Create button
 		let this = Self::alloc(mtm).set_ivars(LayoutButtonIvars::new(workspace.clone()));
@@ -104,6 +117,10 @@ impl LayoutButton {
 		if let Some(font) = font {
 			this.setFont(Some(font));
 		}
+		let text_color = colors.text.as_deref();
+		if let Some(color) = text_color.and_then(|c| ns_color_from_color(c, system_colors)) {
+			this.setContentTintColor(Some(&color));
+		}
 
 		// Set up action handler
 		unsafe { this.setTarget(Some(&this)) };
@@ -113,18 +130,29 @@ impl LayoutButton {
 		this.setBordered(false);
 		this.setWantsLayer(true);
 		let layer = this.layer().unwrap();
-		let _: () = unsafe { msg_send![&layer, setCornerRadius: 4.0] };
+		let corner_radius = metrics.corner_radius.map(f64::from).unwrap_or(4.0);
+		let _: () = unsafe { msg_send![&layer, setCornerRadius: corner_radius] };
 
 		// Add size constraints for padding (since bezel is removed)
+		let (min_width, min_height) = min_size(&this, metrics, 0.0, WorkspaceButton::HEIGHT);
+		let width_constraint = this
+			.widthAnchor()
+			.constraintGreaterThanOrEqualToConstant(min_width);
 		let height_constraint = this
 			.heightAnchor()
-			.constraintGreaterThanOrEqualToConstant(24.0);
+			.constraintGreaterThanOrEqualToConstant(min_height);
+		width_constraint.setActive(true);
 		height_constraint.setActive(true);
 
 		// Set background color based on active state
 		let bg_color = NSColor::clearColor().CGColor();
 		let _: () = unsafe { msg_send![&layer, setBackgroundColor: &*bg_color] };
 
+		let hover_color = colors.hover_background.as_deref();
+		if let Some(color) = hover_color.and_then(|c| ns_color_from_color(c, system_colors)) {
+			let _ = this.ivars().hover_background_color.set(color);
+		}
+
 		this
 	}
 }
@@ -14,9 +14,10 @@ use objc2_foundation::{
 	NSSize, NSString,
 };
 
-use self::workspace_button::WorkspaceButton;
+use self::workspace_button::{WorkspaceButton, WorkspaceButtonStyle};
 use self::workspaces_stack_view::WorkspacesStackView;
 use crate::config::Config;
+use crate::label::{format_label, DEFAULT_LABEL_FORMAT};
 use crate::macos::layout_button::LayoutButton;
 use crate::macos::windows::settings::SettingsWindowController;
 
@@ -70,7 +71,7 @@ define_class!(
 			let stack_view = {
 				let stack = WorkspacesStackView::new(mtm);
 				stack.setOrientation(NSUserInterfaceLayoutOrientation::Horizontal);
-				stack.setSpacing(2.0);
+				stack.setSpacing(WorkspacesStackView::SPACING);
 				stack
 			};
 
@@ -83,22 +84,32 @@ define_class!(
 
 			let config = Config::load().unwrap_or_default();
 
-			// Resolve custom font lazily once at startup.
-			let custom_font = config.font_family.as_deref().and_then(|family| {
+			// Resolve custom font lazily once at startup, using the first available
+			// family in the chain. AppKit falls back to system fonts for missing glyphs.
+			let font_size = config.font_size.map(f64::from);
+			let custom_font = config.font_family.as_ref().and_then(|family| {
 				let weight = config.font_weight.unwrap_or(400);
-				let size = NSFont::systemFontOfSize(0.0).pointSize();
-				let postscript_name = crate::utils::find_font(family, weight)?.postscript_name()?;
-				NSFont::fontWithName_size(&NSString::from_str(&postscript_name), size)
+				let size = font_size.unwrap_or_else(|| NSFont::systemFontOfSize(0.0).pointSize());
+				family.families().into_iter().find_map(|family| {
+					let font = crate::utils::find_font(family, weight)?;
+					let postscript_name = font.postscript_name()?;
+					NSFont::fontWithName_size(&NSString::from_str(&postscript_name), size)
+				})
 			});
+			// Use system font with the custom size if no custom font is available.
+			let custom_font = custom_font.or_else(|| font_size.map(NSFont::systemFontOfSize));
 			let _ = self.ivars().custom_font.set(custom_font);
 			let _ = self.ivars().config.set(config);
 
 			self.update_workspace_buttons(komorebi_state);
 
 			std::thread::spawn(|| {
-				crate::komorebi::listen_for_state(|new_state| {
-					Queue::main().exec_async(|| AppDelegate::dispatch_new_state(new_state));
-				})
+				crate::komorebi::listen_for_state(
+					|new_state| {
+						Queue::main().exec_async(|| AppDelegate::dispatch_new_state(new_state));
+					},
+					|_| {},
+				)
 			});
 		}
 	}
@@ -144,6 +155,9 @@ impl AppDelegate {
 			button.removeFromSuperview();
 		}
 
+		let spacing = config.metrics.spacing.map(f64::from);
+		stack_view.setSpacing(spacing.unwrap_or(WorkspacesStackView::SPACING));
+
 		views.clear();
 
 		let Some(monitor) = state.monitors.first() else {
@@ -152,21 +166,54 @@ impl AppDelegate {
 
 		// Use the cached resolved custom font
 		let custom_font = self.ivars().custom_font.get().and_then(|f| f.as_deref());
-		let active_indicator_color = config.colors.active_indicator.as_deref();
-		let busy_indicator_color = config.colors.busy_indicator.as_deref();
+		let dark_mode = utils::is_dark_mode(mtm);
+		let colors = config.get_colors(&monitor.id, dark_mode, state.theme.as_ref());
+		let system_colors = utils::system_colors();
+		let active_indicator_color = colors.active_indicator.as_deref();
+
+		// Use unfocused colors and opacity if this monitor is not focused
+		let (unfocused_active_indicator_color, indicator_opacity) = if monitor.focused {
+			(None, 1.0)
+		} else {
+			let color = config.colors.unfocused_active_indicator.as_deref();
+			let opacity = config.colors.unfocused_opacity.unwrap_or(1.0);
+			(color, f64::from(opacity))
+		};
+
+		let label_format = config
+			.label_format
+			.as_deref()
+			.unwrap_or(DEFAULT_LABEL_FORMAT);
 
 		for workspace in &monitor.workspaces {
 			if config.hide_empty_workspaces && workspace.is_empty && !workspace.focused {
 				continue;
 			}
 
-			let workspace_button = WorkspaceButton::new(
-				mtm,
-				workspace,
-				custom_font,
+			let workspace_config = config.get_workspace(&monitor.id, workspace);
+			if workspace_config.hidden == Some(true) && !workspace.focused {
+				continue;
+			}
+
+			let label_format = workspace_config.label.as_deref().unwrap_or(label_format);
+			let icon = config.get_icon(&workspace_config, workspace);
+			let label = format_label(label_format, workspace, icon);
+
+			let active_indicator_color = unfocused_active_indicator_color
+				.or(workspace_config.active_indicator.as_deref())
+				.or(active_indicator_color);
+
+			let style = WorkspaceButtonStyle {
+				font: custom_font,
+				metrics: &config.metrics,
+				colors: &colors,
+				system_colors: &system_colors,
 				active_indicator_color,
-				busy_indicator_color,
-			);
+				indicator_opacity,
+				indicator_style: config.indicator_style,
+			};
+
+			let workspace_button = WorkspaceButton::new(mtm, workspace, &label, style);
 			stack_view.addArrangedSubview(&workspace_button);
 			views.push(workspace_button.downcast().unwrap());
 		}
@@ -178,7 +225,14 @@ impl AppDelegate {
 				stack_view.addArrangedSubview(&separator);
 				views.push(separator.downcast().unwrap());
 
-				let layout_button = LayoutButton::new(mtm, focused_ws, custom_font);
+				let layout_button = LayoutButton::new(
+					mtm,
+					focused_ws,
+					custom_font,
+					&config.metrics,
+					&colors,
+					&system_colors,
+				);
 				stack_view.addArrangedSubview(&layout_button);
 				views.push(layout_button.downcast().unwrap());
 			}
@@ -187,7 +241,9 @@ impl AppDelegate {
 		let ns_status_item = self.ivars().ns_status_item.get().unwrap();
 		if let Some(btn) = ns_status_item.button(mtm) {
 			let fitting_size = stack_view.fittingSize();
-			let size = NSSize::new(fitting_size.width, WorkspaceButton::HEIGHT);
+			let height = config.metrics.min_height.map(f64::from);
+			let height = height.unwrap_or(WorkspaceButton::HEIGHT);
+			let size = NSSize::new(fitting_size.width, height);
 			let frame = NSRect::new(NSPoint::new(0.0, 0.0), size);
 			stack_view.setFrame(frame);
 			btn.setFrame(frame);
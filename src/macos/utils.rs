@@ -1,11 +1,12 @@
 use objc2::rc::Retained;
-use objc2_app_kit::NSColor;
+use objc2_app_kit::{NSApp, NSAppearanceNameDarkAqua, NSColor, NSColorSpace};
+use objc2_foundation::{MainThreadMarker, NSArray};
 
-pub fn ns_color_from_color(color: &str) -> Option<Retained<NSColor>> {
-	let rgba = color::parse_color(color)
-		.ok()?
-		.to_alpha_color::<color::Srgb>()
-		.to_rgba8();
+use crate::color_expr::{adjust_lightness, resolve_color, Color, SystemColors};
+
+/// Resolves a color expression into an `NSColor`, see [`crate::color_expr::ColorExpr`].
+pub fn ns_color_from_color(color: &str, system: &SystemColors) -> Option<Retained<NSColor>> {
+	let rgba = resolve_color(color, system)?.to_rgba8();
 	Some(NSColor::colorWithSRGBRed_green_blue_alpha(
 		f64::from(rgba.r) / 255.0,
 		f64::from(rgba.g) / 255.0,
@@ -13,3 +14,39 @@ pub fn ns_color_from_color(color: &str) -> Option<Retained<NSColor>> {
 		f64::from(rgba.a) / 255.0,
 	))
 }
+
+/// Determines if the system is in dark mode, from the app's effective appearance.
+pub fn is_dark_mode(mtm: MainThreadMarker) -> bool {
+	let appearance = NSApp(mtm).effectiveAppearance();
+	let names = NSArray::from_slice(&[unsafe { NSAppearanceNameDarkAqua }]);
+	appearance
+		.bestMatchFromAppearancesWithNames(&names)
+		.is_some_and(|name| &*name == unsafe { NSAppearanceNameDarkAqua })
+}
+
+/// Converts an `NSColor` to an sRGB color, if it can be represented in sRGB.
+fn color_from_ns_color(color: &NSColor) -> Option<Color> {
+	let color = color.colorUsingColorSpace(&NSColorSpace::sRGBColorSpace())?;
+	Some(Color::new([
+		color.redComponent() as f32,
+		color.greenComponent() as f32,
+		color.blueComponent() as f32,
+		color.alphaComponent() as f32,
+	]))
+}
+
+/// Gets the system colors referenced by color expressions in the config.
+///
+/// macOS has no accent color variants, so they are derived from the accent color.
+pub fn system_colors() -> SystemColors {
+	let accent = color_from_ns_color(&NSColor::controlAccentColor());
+	let variant = |amount: f32| accent.map(|accent| adjust_lightness(accent, amount));
+
+	SystemColors {
+		accent,
+		accent_light: [variant(0.1), variant(0.2), variant(0.3)],
+		accent_dark: [variant(-0.1), variant(-0.2), variant(-0.3)],
+		foreground: color_from_ns_color(&NSColor::labelColor()),
+		background: color_from_ns_color(&NSColor::windowBackgroundColor()),
+	}
+}
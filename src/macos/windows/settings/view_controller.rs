@@ -43,6 +43,8 @@ pub struct SettingsViewControllerIvars {
 	hide_empty_workspaces_checkbox: RefCell<Option<Retained<NSButton>>>,
 	font_family_field: RefCell<Option<Retained<NSTextField>>>,
 	font_weight_field: RefCell<Option<Retained<NSTextField>>>,
+	font_size_field: RefCell<Option<Retained<NSTextField>>>,
+	label_format_field: RefCell<Option<Retained<NSTextField>>>,
 	active_indicator_color_field: RefCell<Option<Retained<NSTextField>>>,
 	busy_indicator_color_field: RefCell<Option<Retained<NSTextField>>>,
 }
@@ -55,6 +57,8 @@ impl SettingsViewControllerIvars {
 			hide_empty_workspaces_checkbox: RefCell::new(None),
 			font_family_field: RefCell::new(None),
 			font_weight_field: RefCell::new(None),
+			font_size_field: RefCell::new(None),
+			label_format_field: RefCell::new(None),
 			active_indicator_color_field: RefCell::new(None),
 			busy_indicator_color_field: RefCell::new(None),
 		}
@@ -175,8 +179,9 @@ impl SettingsViewController {
 		let family_row = self.create_hstack();
 		let family_label = NSString::from_str("Font Family");
 		let family_label = NSTextField::labelWithString(&family_label, self.mtm());
-		let family_value = config.font_family.as_deref().unwrap_or("");
-		let family_field = self.create_text_field("e.g. Roboto", family_value);
+		let family_value = config.font_family.as_ref().map(ToString::to_string);
+		let family_value = family_value.unwrap_or_default();
+		let family_field = self.create_text_field("e.g. Roboto, Apple Color Emoji", &family_value);
 		family_row.addArrangedSubview(&family_label);
 		family_row.addArrangedSubview(&family_field);
 		vstack.addArrangedSubview(&family_row);
@@ -193,6 +198,28 @@ impl SettingsViewController {
 		vstack.addArrangedSubview(&weight_row);
 		*self.ivars().font_weight_field.borrow_mut() = Some(weight_field);
 
+		// Font size input
+		let size_row = self.create_hstack();
+		let size_label = NSString::from_str("Font Size");
+		let size_label = NSTextField::labelWithString(&size_label, self.mtm());
+		let size_value = config.font_size.map(|s| s.to_string()).unwrap_or_default();
+		let size_field = self.create_text_field("4-72", &size_value);
+		size_row.addArrangedSubview(&size_label);
+		size_row.addArrangedSubview(&size_field);
+		vstack.addArrangedSubview(&size_row);
+		*self.ivars().font_size_field.borrow_mut() = Some(size_field);
+
+		// Label format input
+		let label_format_row = self.create_hstack();
+		let label_format_label = NSString::from_str("Label Format");
+		let label_format_label = NSTextField::labelWithString(&label_format_label, self.mtm());
+		let label_format_value = config.label_format.as_deref().unwrap_or("");
+		let label_format_field = self.create_text_field("e.g. {index}: {name}", label_format_value);
+		label_format_row.addArrangedSubview(&label_format_label);
+		label_format_row.addArrangedSubview(&label_format_field);
+		vstack.addArrangedSubview(&label_format_row);
+		*self.ivars().label_format_field.borrow_mut() = Some(label_format_field);
+
 		let active_indicator_row = self.create_hstack();
 		let active_indicator_label = NSString::from_str("Active Indicator");
 		let active_indicator_label =
@@ -246,12 +273,28 @@ impl SettingsViewController {
 		}
 		if let Some(field) = self.ivars().font_family_field.borrow().as_ref() {
 			let value = field.stringValue().to_string();
-			config.font_family = if value.is_empty() { None } else { Some(value) };
+			config.font_family = crate::config::FontFamily::from_text(&value);
 		}
 		if let Some(field) = self.ivars().font_weight_field.borrow().as_ref() {
 			let value = field.stringValue().to_string();
 			config.font_weight = value.parse::<u16>().ok();
 		}
+		if let Some(field) = self.ivars().font_size_field.borrow().as_ref() {
+			let value = field.stringValue().to_string();
+			let value = value.trim();
+			config.font_size = if value.is_empty() {
+				None
+			} else {
+				let size = value.parse::<f32>().context("invalid font size")?;
+				let range = crate::config::FONT_SIZE_RANGE;
+				anyhow::ensure!(range.contains(&size), "font size must be in {range:?}");
+				Some(size)
+			};
+		}
+		if let Some(field) = self.ivars().label_format_field.borrow().as_ref() {
+			let value = field.stringValue().to_string();
+			config.label_format = if value.is_empty() { None } else { Some(value) };
+		}
 		if let Some(field) = self.ivars().active_indicator_color_field.borrow().as_ref() {
 			let value = field.stringValue().to_string();
 			let value = value.trim();
@@ -7,10 +7,18 @@ use objc2_app_kit::{
 };
 use objc2_foundation::{MainThreadMarker, NSObjectProtocol, NSPoint, NSRect, NSSize, NSString};
 
+use crate::color_expr::SystemColors;
+use crate::config::{IndicatorStyle, MetricsConfig};
+use crate::macos::utils::ns_color_from_color;
+use crate::theme::ThemeColors;
+
 #[derive(Debug)]
 pub struct WorkspaceButtonIvars {
 	workspace: crate::komorebi::Workspace,
 	indicator: OnceCell<Retained<NSView>>,
+	/// Background color of the button when not hovered.
+	background_color: OnceCell<Retained<NSColor>>,
+	hover_background_color: OnceCell<Retained<NSColor>>,
 	is_hovering: Cell<bool>,
 	tracking_area: RefCell<Option<Retained<NSTrackingArea>>>,
 }
@@ -20,6 +28,8 @@ impl WorkspaceButtonIvars {
 		Self {
 			workspace,
 			indicator: OnceCell::new(),
+			background_color: OnceCell::new(),
+			hover_background_color: OnceCell::new(),
 			is_hovering: Cell::new(false),
 			tracking_area: RefCell::new(None),
 		}
@@ -47,20 +57,22 @@ define_class!(
 		fn mouse_entered(&self, _event: &NSEvent) {
 			self.ivars().is_hovering.set(true);
 			let layer = self.layer().unwrap();
-			let hover_color = NSColor::colorWithWhite_alpha(1.0, 0.1).CGColor();
+			let hover_color = match self.ivars().hover_background_color.get() {
+				Some(color) => color.CGColor(),
+				None => NSColor::colorWithWhite_alpha(1.0, 0.1).CGColor(),
+			};
 			let _: () = unsafe { msg_send![&layer, setBackgroundColor: &*hover_color] };
 		}
 
 		#[unsafe(method(mouseExited:))]
 		fn mouse_exited(&self, _event: &NSEvent) {
-			if self.ivars().workspace.focused {
-				return;
-			}
-
 			self.ivars().is_hovering.set(false);
 			let layer = self.layer().unwrap();
-			let clear_color = NSColor::clearColor().CGColor();
-			let _: () = unsafe { msg_send![&layer, setBackgroundColor: &*clear_color] };
+			let bg_color = match self.ivars().background_color.get() {
+				Some(color) => color.CGColor(),
+				None => NSColor::clearColor().CGColor(),
+			};
+			let _: () = unsafe { msg_send![&layer, setBackgroundColor: &*bg_color] };
 		}
 
 		#[unsafe(method(updateTrackingAreas))]
@@ -90,29 +102,68 @@ define_class!(
 	}
 );
 
+/// Visual options of a [`WorkspaceButton`], resolved from the config.
+pub struct WorkspaceButtonStyle<'a> {
+	pub font: Option<&'a NSFont>,
+	pub metrics: &'a MetricsConfig,
+	/// Resolved theme colors, the active indicator color is resolved separately.
+	pub colors: &'a ThemeColors,
+	/// System colors referenced by the color expressions.
+	pub system_colors: &'a SystemColors,
+	pub active_indicator_color: Option<&'a str>,
+	/// Opacity of the indicator, used to dim it when the monitor is not focused.
+	pub indicator_opacity: f64,
+	pub indicator_style: IndicatorStyle,
+}
+
 impl WorkspaceButton {
 	const INDICATOR_SIZE: f64 = 4.0;
+	const MIN_WIDTH: f64 = 32.0;
+	const CORNER_RADIUS: f64 = 4.0;
 	pub const HEIGHT: f64 = 24.0;
 
 	pub fn new(
 		mtm: MainThreadMarker,
 		workspace: &crate::komorebi::Workspace,
-		font: Option<&NSFont>,
-		active_indicator_color: Option<&str>,
-		busy_indicator_color: Option<&str>,
+		label: &str,
+		style: WorkspaceButtonStyle,
 	) -> Retained<Self> {
+		let WorkspaceButtonStyle {
+			font,
+			metrics,
+			colors,
+			system_colors,
+			active_indicator_color,
+			indicator_opacity,
+			indicator_style,
+		} = style;
+
 		// Create button
 		let this = Self::alloc(mtm).set_ivars(WorkspaceButtonIvars::new(workspace.clone()));
 		// SAFETY: The signature of `NSButton`'s `init` method is correct.
 		let this: Retained<Self> = unsafe { msg_send![super(this), init] };
 
 		// Configure button
-		this.setTitle(&NSString::from_str(&workspace.name));
+		this.setTitle(&NSString::from_str(label));
 		this.setTag(workspace.index as isize);
 		if let Some(font) = font {
 			this.setFont(Some(font));
 		}
 
+		// Set text color based on workspace state
+		let text_color = if workspace.focused {
+			colors.text.as_deref()
+		} else if workspace.is_empty {
+			let color = colors.empty_text.as_deref();
+			color.or(colors.inactive_text.as_deref())
+		} else {
+			colors.inactive_text.as_deref()
+		};
+		let text_color = text_color.or(colors.text.as_deref());
+		if let Some(color) = text_color.and_then(|c| ns_color_from_color(c, system_colors)) {
+			this.setContentTintColor(Some(&color));
+		}
+
 		// Set up action handler
 		unsafe { this.setTarget(Some(&this)) };
 		unsafe { this.setAction(Some(sel!(buttonClicked:))) };
@@ -121,61 +172,122 @@ impl WorkspaceButton {
 		this.setBordered(false);
 		this.setWantsLayer(true);
 		let layer = this.layer().unwrap();
-		let _: () = unsafe { msg_send![&layer, setCornerRadius: 4.0] };
+		let corner_radius = metrics.corner_radius.map(f64::from);
+		let corner_radius = corner_radius.unwrap_or(Self::CORNER_RADIUS);
+		let _: () = unsafe { msg_send![&layer, setCornerRadius: corner_radius] };
 
 		// Add size constraints for padding (since bezel is removed)
+		let (min_width, min_height) = min_size(&this, metrics, Self::MIN_WIDTH, Self::HEIGHT);
 		let width_constraint = this
 			.widthAnchor()
-			.constraintGreaterThanOrEqualToConstant(32.0);
+			.constraintGreaterThanOrEqualToConstant(min_width);
 		let height_constraint = this
 			.heightAnchor()
-			.constraintGreaterThanOrEqualToConstant(Self::HEIGHT);
+			.constraintGreaterThanOrEqualToConstant(min_height);
 		width_constraint.setActive(true);
 		height_constraint.setActive(true);
 
-		// Set background color based on active state
-		let bg_color = if workspace.focused {
-			NSColor::colorWithWhite_alpha(1.0, 0.1).CGColor()
+		// Resolve indicator color based on workspace state
+		let indicator_color = if workspace.focused {
+			let color = active_indicator_color.and_then(|c| ns_color_from_color(c, system_colors));
+			Some(color.unwrap_or_else(NSColor::systemBlueColor))
+		} else if !workspace.is_empty {
+			let color = colors
+				.busy_indicator
+				.as_deref()
+				.and_then(|c| ns_color_from_color(c, system_colors));
+			Some(color.unwrap_or_else(NSColor::lightGrayColor))
 		} else {
-			NSColor::clearColor().CGColor()
+			None
 		};
-		let _: () = unsafe { msg_send![&layer, setBackgroundColor: &*bg_color] };
+
+		// Dim indicator, used when the monitor is not focused
+		let indicator_opacity = indicator_opacity.clamp(0.0, 1.0);
+		let indicator_color = indicator_color.map(|color| {
+			let alpha = color.alphaComponent() * indicator_opacity;
+			color.colorWithAlphaComponent(alpha)
+		});
+
+		// Set background color based on active state, the pill style
+		// uses the indicator color as the button background instead
+		let bg_color = match (&indicator_color, indicator_style) {
+			(Some(color), IndicatorStyle::Pill) => color.clone(),
+			_ if workspace.focused => colors
+				.focused_background
+				.as_deref()
+				.and_then(|c| ns_color_from_color(c, system_colors))
+				.unwrap_or_else(|| NSColor::colorWithWhite_alpha(1.0, 0.1)),
+			_ => NSColor::clearColor(),
+		};
+		let _: () = unsafe { msg_send![&layer, setBackgroundColor: &*bg_color.CGColor()] };
+		let _ = this.ivars().background_color.set(bg_color);
+
+		let hover_color = colors.hover_background.as_deref();
+		if let Some(color) = hover_color.and_then(|c| ns_color_from_color(c, system_colors)) {
+			let _ = this.ivars().hover_background_color.set(color);
+		}
+
+		if indicator_style == IndicatorStyle::Pill {
+			let (_, height) = min_size(&this, metrics, Self::MIN_WIDTH, Self::HEIGHT);
+			let _: () = unsafe { msg_send![&layer, setCornerRadius: height / 2.0] };
+		}
+
+		// Outline the button for the box style
+		if let (Some(color), IndicatorStyle::Box) = (&indicator_color, indicator_style) {
+			let border_width = metrics.indicator_height.map(f64::from);
+			let border_width = (border_width.unwrap_or(Self::INDICATOR_SIZE) / 2.0).max(1.0);
+			let _: () = unsafe { msg_send![&layer, setBorderWidth: border_width] };
+			let _: () = unsafe { msg_send![&layer, setBorderColor: &*color.CGColor()] };
+		}
 
 		// Create indicator view
 		let indicator = NSView::new(mtm);
 
-		// Make it round
 		indicator.setWantsLayer(true);
 		let layer = indicator.layer().unwrap();
-		let _: () = unsafe { msg_send![&layer, setCornerRadius: 2.0] };
-
-		// Set indicator color and visibility based on workspace state
-		if workspace.focused {
-			let color = active_indicator_color
-				.and_then(crate::macos::utils::ns_color_from_color)
-				.unwrap_or_else(NSColor::systemBlueColor)
-				.CGColor();
-			let blue = color;
-			let _: () = unsafe { msg_send![&layer, setBackgroundColor: &*blue] };
-			indicator.setHidden(false);
-		} else if !workspace.is_empty {
-			let color = busy_indicator_color
-				.and_then(crate::macos::utils::ns_color_from_color)
-				.unwrap_or_else(NSColor::lightGrayColor)
-				.CGColor();
-			let light_gray = color;
-			let _: () = unsafe { msg_send![&layer, setBackgroundColor: &*light_gray] };
-			indicator.setHidden(false);
-		} else {
-			indicator.setHidden(true);
+
+		// Set indicator color and visibility based on workspace state and style
+		let has_indicator_view = !matches!(
+			indicator_style,
+			IndicatorStyle::Pill | IndicatorStyle::Box | IndicatorStyle::None
+		);
+		match &indicator_color {
+			Some(color) if has_indicator_view => {
+				let _: () = unsafe { msg_send![&layer, setBackgroundColor: &*color.CGColor()] };
+				indicator.setHidden(false);
+			}
+			_ => indicator.setHidden(true),
 		}
 
-		// Position indicator at bottom center of button
+		// Determine indicator size
 		let btn_fitting_size = this.fittingSize();
-		let x = (btn_fitting_size.width - Self::INDICATOR_SIZE) / 2.0;
-		let y = btn_fitting_size.height - Self::INDICATOR_SIZE;
+		let indicator_width = if workspace.focused {
+			metrics.indicator_active_width
+		} else {
+			metrics.indicator_width
+		};
+		let indicator_width = indicator_width.map(f64::from);
+		let indicator_width = indicator_width.unwrap_or(Self::INDICATOR_SIZE);
+		let indicator_height = metrics.indicator_height.map(f64::from);
+		let indicator_height = indicator_height.unwrap_or(Self::INDICATOR_SIZE);
+		let (indicator_width, indicator_height) = match indicator_style {
+			IndicatorStyle::Underline if workspace.focused => {
+				(btn_fitting_size.width, indicator_height)
+			}
+			IndicatorStyle::Dot => (indicator_height, indicator_height),
+			_ => (indicator_width, indicator_height),
+		};
+		let _: () = unsafe { msg_send![&layer, setCornerRadius: indicator_height / 2.0] };
+
+		// Position indicator at the bottom center of the button,
+		// or at the top for the top line style (the button view is flipped)
+		let x = (btn_fitting_size.width - indicator_width) / 2.0;
+		let y = match indicator_style {
+			IndicatorStyle::TopLine => 0.0,
+			_ => btn_fitting_size.height - indicator_height,
+		};
 		let position = NSPoint::new(x, y);
-		let size = NSSize::new(Self::INDICATOR_SIZE, Self::INDICATOR_SIZE);
+		let size = NSSize::new(indicator_width, indicator_height);
 		indicator.setFrame(NSRect::new(position, size));
 
 		// Add indicator to button
@@ -187,3 +299,28 @@ impl WorkspaceButton {
 		this
 	}
 }
+
+/// Computes the minimum size of a button from the metrics config, taking the
+/// padding around the button content into account.
+pub fn min_size(
+	button: &NSButton,
+	metrics: &MetricsConfig,
+	default_width: f64,
+	default_height: f64,
+) -> (f64, f64) {
+	let content_size = button.fittingSize();
+
+	let min_width = metrics.min_width.map(f64::from).unwrap_or(default_width);
+	let min_height = metrics.min_height.map(f64::from).unwrap_or(default_height);
+
+	let width = match metrics.padding_x {
+		Some(padding) => min_width.max(content_size.width + f64::from(padding)),
+		None => min_width,
+	};
+	let height = match metrics.padding_y {
+		Some(padding) => min_height.max(content_size.height + f64::from(padding)),
+		None => min_height,
+	};
+
+	(width, height)
+}
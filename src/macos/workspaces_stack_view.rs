@@ -43,6 +43,9 @@ define_class!(
 );
 
 impl WorkspacesStackView {
+	/// Default spacing between buttons.
+	pub const SPACING: f64 = 2.0;
+
 	pub fn new(mtm: MainThreadMarker) -> Retained<Self> {
 		let this = Self::alloc(mtm).set_ivars(WorkspacesStackViewIvars::default());
 		let this: Retained<Self> = unsafe { msg_send![super(this), init] };
@@ -1,13 +1,23 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
-use tracing::level_filters::LevelFilter;
-use tracing_subscriber::EnvFilter;
-
+mod color_expr;
 mod config;
+mod crash;
+mod icons;
 mod komorebi;
+mod label;
+mod logging;
 #[cfg(target_os = "macos")]
 mod macos;
+mod notifications;
+#[cfg(any(windows, test))]
+mod rasterizer;
+#[cfg(any(windows, test))]
+mod redact;
+mod theme;
 mod utils;
+#[cfg(any(windows, test))]
+mod widgets;
 #[cfg(target_os = "windows")]
 mod windows;
 
@@ -22,70 +32,27 @@ fn run() -> anyhow::Result<()> {
 }
 
 fn main() -> anyhow::Result<()> {
-	let env_filter = EnvFilter::try_from_env("KOMOREBI_SWITCHER_LOG").unwrap_or_else(|_| {
-		EnvFilter::builder()
-			.with_default_directive(LevelFilter::DEBUG.into())
-			.from_env_lossy()
-	});
-
-	let subscriber = tracing_subscriber::fmt()
-		.compact()
-		.with_max_level(tracing::Level::TRACE)
-		.with_target(false)
-		.with_env_filter(env_filter)
-		.finish();
-
-	#[cfg(not(debug_assertions))]
-	let (file_log_layer, _f_guard) = {
-		use std::time::{Duration, SystemTime};
-
-		use anyhow::Context;
+	let logging_config = config::Config::load_logging();
+	let _log_guard = logging::init(&logging_config)?;
 
-		let logs_dir = dirs::data_dir()
-			.context("Failed to get $data_dir path")?
-			.join("komorebi-switcher")
-			.join("logs");
+	std::panic::set_hook(Box::new(|info| {
+		tracing::error!("{info}");
 
-		const MONTH: Duration = Duration::from_secs(60 * 60 * 24 * 30);
-		let now = SystemTime::now();
+		// Panics are fatal on the main thread, and on all threads when aborting,
+		// others only stop their thread (i.e the komorebi listener in debug builds)
+		let fatal = cfg!(panic = "abort") || std::thread::current().name() == Some("main");
 
-		// remove logs older than a month
-		for entry in std::fs::read_dir(&logs_dir).ok().into_iter().flatten() {
-			let Ok(entry) = entry else {
-				continue;
-			};
-			let Ok(modified_time) = entry.metadata().and_then(|m| m.modified()) else {
-				continue;
-			};
+		let report = match crash::write_report(info, fatal) {
+			Ok(report) => format!("A crash report was saved to {}", report.display()),
+			Err(e) => format!("Failed to write a crash report: {e}"),
+		};
+		tracing::error!("{report}");
 
-			let elapsed = now.duration_since(modified_time).unwrap_or_default();
-			if elapsed > MONTH {
-				let _ = std::fs::remove_file(entry.path());
-			}
+		if fatal {
+			utils::error_dialog(format!("{info}\n\n{report}"));
+		} else {
+			notifications::error(format!("{info}\n{report}"));
 		}
-
-		let appender = tracing_appender::rolling::daily(&logs_dir, "komorebi-switcher.log");
-		let (non_blocking, _guard) = tracing_appender::non_blocking(appender);
-		let layer = tracing_subscriber::fmt::Layer::default()
-			// disable ansi coloring in log file
-			.with_ansi(false)
-			.with_writer(non_blocking);
-
-		(layer, _guard)
-	};
-
-	#[cfg(not(debug_assertions))]
-	use tracing_subscriber::layer::SubscriberExt;
-	#[cfg(not(debug_assertions))]
-	let subscriber = subscriber.with(file_log_layer);
-
-	tracing::subscriber::set_global_default(subscriber)?;
-
-	tracing::debug!("Initialized Logger");
-
-	std::panic::set_hook(Box::new(|info| {
-		utils::error_dialog(info);
-		tracing::error!("{info}");
 	}));
 
 	if let Err(e) = run() {
@@ -0,0 +1,130 @@
+//! Errors and warnings shown to the user without blocking the app,
+//! only on Windows for now, where `set_handler` sets how they are shown.
+
+use std::collections::{BTreeMap, VecDeque};
+#[cfg(windows)]
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Time during which a notification isn't shown again.
+const DEDUP_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Maximum number of notifications shown within [`RATE_WINDOW`],
+/// the others are dropped.
+const RATE_LIMIT: usize = 5;
+const RATE_WINDOW: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+	Warning,
+	Error,
+}
+
+/// Action offered by a notification, shown as a button.
+#[cfg(windows)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Action {
+	/// Opens a folder in the file explorer.
+	OpenFolder(PathBuf),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Notification {
+	pub severity: Severity,
+	pub message: String,
+	#[cfg(windows)]
+	pub action: Option<Action>,
+}
+
+type Handler = Arc<dyn Fn(Notification) + Send + Sync>;
+
+struct Notifier {
+	handler: Option<Handler>,
+	/// When each message was last shown.
+	shown: BTreeMap<String, Instant>,
+	/// Times at which the notifications in the last [`RATE_WINDOW`] were shown.
+	recent: VecDeque<Instant>,
+}
+
+static NOTIFIER: Mutex<Notifier> = Mutex::new(Notifier {
+	handler: None,
+	shown: BTreeMap::new(),
+	recent: VecDeque::new(),
+});
+
+/// Sets how notifications are shown, they are dropped until it is set.
+#[cfg(windows)]
+pub fn set_handler(handler: impl Fn(Notification) + Send + Sync + 'static) {
+	NOTIFIER.lock().unwrap().handler = Some(Arc::new(handler));
+}
+
+/// Shows a warning, the caller is expected to log it.
+pub fn warn(message: impl Into<String>) {
+	notify(Notification {
+		severity: Severity::Warning,
+		message: message.into(),
+		#[cfg(windows)]
+		action: None,
+	});
+}
+
+/// Shows an error, the caller is expected to log it.
+pub fn error(message: impl Into<String>) {
+	notify(Notification {
+		severity: Severity::Error,
+		message: message.into(),
+		#[cfg(windows)]
+		action: None,
+	});
+}
+
+/// Shows an error with an action, the caller is expected to log it.
+#[cfg(windows)]
+pub fn error_with_action(message: impl Into<String>, action: Action) {
+	notify(Notification {
+		severity: Severity::Error,
+		message: message.into(),
+		action: Some(action),
+	});
+}
+
+fn notify(notification: Notification) {
+	let now = Instant::now();
+	let message = &notification.message;
+
+	let handler = {
+		let mut notifier = NOTIFIER.lock().unwrap();
+		let Some(handler) = notifier.handler.clone() else {
+			tracing::debug!("No notification handler, dropping: {message}");
+			return;
+		};
+
+		notifier
+			.shown
+			.retain(|_, shown_at| now - *shown_at < DEDUP_INTERVAL);
+		if notifier.shown.contains_key(message) {
+			return;
+		}
+
+		while notifier
+			.recent
+			.front()
+			.is_some_and(|at| now - *at > RATE_WINDOW)
+		{
+			notifier.recent.pop_front();
+		}
+		if notifier.recent.len() >= RATE_LIMIT {
+			tracing::debug!("Too many notifications, dropping: {message}");
+			return;
+		}
+
+		notifier.shown.insert(message.clone(), now);
+		notifier.recent.push_back(now);
+
+		handler
+	};
+
+	// Called without the lock, in case the handler notifies too
+	handler(notification);
+}
@@ -0,0 +1,540 @@
+use std::collections::HashMap;
+
+use egui::epaint::{ClippedPrimitive, ImageDelta, Primitive, Vertex};
+use egui::{Color32, ImageData, Pos2, TextureFilter, TextureId};
+use tiny_skia::Pixmap;
+
+struct Texture {
+	width: usize,
+	height: usize,
+	pixels: Vec<Color32>,
+	filter: TextureFilter,
+}
+
+impl Texture {
+	/// Samples the texture at the given uv coordinates, clamped to its edges,
+	/// returning the premultiplied color.
+	fn sample(&self, uv: Pos2) -> [f32; 4] {
+		let x = uv.x * self.width as f32;
+		let y = uv.y * self.height as f32;
+
+		match self.filter {
+			TextureFilter::Nearest => self.texel(x as isize, y as isize),
+			TextureFilter::Linear => {
+				// Texel centers are at half pixel offsets
+				let (x, y) = (x - 0.5, y - 0.5);
+				let (x0, y0) = (x.floor(), y.floor());
+				let (tx, ty) = (x - x0, y - y0);
+				let (x0, y0) = (x0 as isize, y0 as isize);
+
+				let top = lerp(self.texel(x0, y0), self.texel(x0 + 1, y0), tx);
+				let bottom = lerp(self.texel(x0, y0 + 1), self.texel(x0 + 1, y0 + 1), tx);
+				lerp(top, bottom, ty)
+			}
+		}
+	}
+
+	fn texel(&self, x: isize, y: isize) -> [f32; 4] {
+		let x = x.clamp(0, self.width as isize - 1) as usize;
+		let y = y.clamp(0, self.height as isize - 1) as usize;
+		self.pixels[y * self.width + x].to_array().map(f32::from)
+	}
+}
+
+/// Rasterizes egui meshes on the CPU, used as a fallback when the GPU can't be used.
+///
+/// Colors are blended in gamma space like the egui wgpu renderer does,
+/// and anti-aliasing relies on egui's feathering.
+#[derive(Default)]
+pub struct Rasterizer {
+	textures: HashMap<TextureId, Texture>,
+}
+
+impl Rasterizer {
+	pub fn update_texture(&mut self, id: TextureId, delta: &ImageDelta) {
+		let (width, height, pixels) = match &delta.image {
+			ImageData::Color(image) => (image.width(), image.height(), image.pixels.clone()),
+			ImageData::Font(image) => (
+				image.width(),
+				image.height(),
+				image.srgba_pixels(None).collect(),
+			),
+		};
+
+		let Some([x, y]) = delta.pos else {
+			let filter = delta.options.magnification;
+			let texture = Texture {
+				width,
+				height,
+				pixels,
+				filter,
+			};
+			self.textures.insert(id, texture);
+			return;
+		};
+
+		let Some(texture) = self.textures.get_mut(&id) else {
+			tracing::warn!("Partial update of unknown texture {id:?}");
+			return;
+		};
+
+		for (row, src) in pixels.chunks_exact(width).enumerate() {
+			let start = (y + row) * texture.width + x;
+			texture.pixels[start..start + width].copy_from_slice(src);
+		}
+	}
+
+	pub fn free_texture(&mut self, id: &TextureId) {
+		self.textures.remove(id);
+	}
+
+	/// Draws the tessellated primitives over the pixmap.
+	pub fn render(
+		&self,
+		pixmap: &mut Pixmap,
+		primitives: &[ClippedPrimitive],
+		pixels_per_point: f32,
+	) {
+		let (width, height) = (pixmap.width() as f32, pixmap.height() as f32);
+
+		for ClippedPrimitive {
+			clip_rect,
+			primitive,
+		} in primitives
+		{
+			let Primitive::Mesh(mesh) = primitive else {
+				continue;
+			};
+
+			let Some(texture) = self.textures.get(&mesh.texture_id) else {
+				tracing::warn!("Mesh uses unknown texture {:?}", mesh.texture_id);
+				continue;
+			};
+
+			let clip = Clip {
+				min_x: (clip_rect.min.x * pixels_per_point)
+					.round()
+					.clamp(0.0, width) as usize,
+				min_y: (clip_rect.min.y * pixels_per_point)
+					.round()
+					.clamp(0.0, height) as usize,
+				max_x: (clip_rect.max.x * pixels_per_point)
+					.round()
+					.clamp(0.0, width) as usize,
+				max_y: (clip_rect.max.y * pixels_per_point)
+					.round()
+					.clamp(0.0, height) as usize,
+			};
+
+			if clip.min_x >= clip.max_x || clip.min_y >= clip.max_y {
+				continue;
+			}
+
+			for indices in mesh.indices.chunks_exact(3) {
+				let triangle =
+					[indices[0], indices[1], indices[2]].map(|i| &mesh.vertices[i as usize]);
+				draw_triangle(pixmap, &clip, texture, triangle, pixels_per_point);
+			}
+		}
+	}
+}
+
+/// Clip rectangle in physical pixels, exclusive of the max edges.
+struct Clip {
+	min_x: usize,
+	min_y: usize,
+	max_x: usize,
+	max_y: usize,
+}
+
+fn draw_triangle(
+	pixmap: &mut Pixmap,
+	clip: &Clip,
+	texture: &Texture,
+	[a, b, c]: [&Vertex; 3],
+	pixels_per_point: f32,
+) {
+	// Wind all triangles the same way for the fill rule below
+	let [a, b, c] = if edge(a.pos, b.pos, c.pos) < 0.0 {
+		[a, c, b]
+	} else {
+		[a, b, c]
+	};
+
+	let (pa, pb, pc) = (
+		a.pos * pixels_per_point,
+		b.pos * pixels_per_point,
+		c.pos * pixels_per_point,
+	);
+
+	let area = edge(pa, pb, pc);
+	if area < f32::EPSILON {
+		return;
+	}
+
+	let min_x = (pa.x.min(pb.x).min(pc.x).floor().max(0.0) as usize).max(clip.min_x);
+	let min_y = (pa.y.min(pb.y).min(pc.y).floor().max(0.0) as usize).max(clip.min_y);
+	let max_x = (pa.x.max(pb.x).max(pc.x).ceil().max(0.0) as usize).min(clip.max_x);
+	let max_y = (pa.y.max(pb.y).max(pc.y).ceil().max(0.0) as usize).min(clip.max_y);
+
+	let colors = [a.color, b.color, c.color].map(|c| c.to_array().map(f32::from));
+
+	let stride = pixmap.width() as usize;
+	let data = pixmap.data_mut();
+
+	for y in min_y..max_y {
+		for x in min_x..max_x {
+			let p = Pos2::new(x as f32 + 0.5, y as f32 + 0.5);
+
+			let (wa, wb, wc) = (edge(pb, pc, p), edge(pc, pa, p), edge(pa, pb, p));
+			if !covers(wa, pb, pc) || !covers(wb, pc, pa) || !covers(wc, pa, pb) {
+				continue;
+			}
+
+			// Barycentric weights
+			let (wa, wb, wc) = (wa / area, wb / area, wc / area);
+
+			let uv = Pos2::new(
+				a.uv.x * wa + b.uv.x * wb + c.uv.x * wc,
+				a.uv.y * wa + b.uv.y * wb + c.uv.y * wc,
+			);
+			let texel = texture.sample(uv);
+
+			let src: [f32; 4] = std::array::from_fn(|i| {
+				let color = colors[0][i] * wa + colors[1][i] * wb + colors[2][i] * wc;
+				color * texel[i] / 255.0
+			});
+
+			let i = (y * stride + x) * 4;
+			let dst = &mut data[i..i + 4];
+			let inv_alpha = 1.0 - src[3] / 255.0;
+			for (dst, src) in dst.iter_mut().zip(src) {
+				*dst = (src + *dst as f32 * inv_alpha).round().clamp(0.0, 255.0) as u8;
+			}
+
+			// Keep the color valid for premultiplied alpha after rounding
+			let alpha = dst[3];
+			for channel in &mut dst[..3] {
+				*channel = (*channel).min(alpha);
+			}
+		}
+	}
+}
+
+/// Twice the signed area of the triangle `a`, `b`, `c`.
+fn edge(a: Pos2, b: Pos2, c: Pos2) -> f32 {
+	(b.x - a.x) * (c.y - a.y) - (b.y - a.y) * (c.x - a.x)
+}
+
+/// Whether a pixel at the given `edge` distance from the edge `a` to `b` is
+/// covered, using a top-left rule so pixels on edges shared by two triangles
+/// are only drawn once.
+fn covers(edge: f32, a: Pos2, b: Pos2) -> bool {
+	let top_left = b.y < a.y || (b.y == a.y && b.x > a.x);
+	edge > 0.0 || (edge == 0.0 && top_left)
+}
+
+fn lerp(a: [f32; 4], b: [f32; 4], t: f32) -> [f32; 4] {
+	std::array::from_fn(|i| a[i] + (b[i] - a[i]) * t)
+}
+
+/// Runs egui offscreen and rasterizes its output onto a transparent pixmap.
+#[cfg(test)]
+pub fn render_offscreen(
+	width: u32,
+	height: u32,
+	pixels_per_point: f32,
+	mut paint: impl FnMut(&egui::Context),
+) -> Pixmap {
+	let ctx = egui::Context::default();
+
+	let mut raw_input = egui::RawInput {
+		screen_rect: Some(egui::Rect::from_min_size(
+			Pos2::ZERO,
+			egui::vec2(width as f32, height as f32) / pixels_per_point,
+		)),
+		..Default::default()
+	};
+	raw_input
+		.viewports
+		.entry(raw_input.viewport_id)
+		.or_default()
+		.native_pixels_per_point = Some(pixels_per_point);
+
+	let full_output = ctx.run(raw_input, |ctx| paint(ctx));
+
+	let mut rasterizer = Rasterizer::default();
+	for (id, delta) in &full_output.textures_delta.set {
+		rasterizer.update_texture(*id, delta);
+	}
+
+	let primitives = ctx.tessellate(full_output.shapes, full_output.pixels_per_point);
+
+	let mut pixmap = Pixmap::new(width, height).unwrap();
+	rasterizer.render(&mut pixmap, &primitives, full_output.pixels_per_point);
+
+	for id in &full_output.textures_delta.free {
+		rasterizer.free_texture(id);
+	}
+
+	pixmap
+}
+
+/// Draws the pixmap as text, each pixel being the character of the closest
+/// color in the palette, with transparent pixels drawn as `.`.
+#[cfg(test)]
+pub fn snapshot(pixmap: &Pixmap, palette: &[(char, Color32)]) -> String {
+	let transparent = ('.', Color32::TRANSPARENT);
+
+	let distance = |pixel: &[u8], color: Color32| {
+		// The rendered colors are clamped to their alpha, see `Rasterizer::render`
+		let [r, g, b, a] = color.to_array();
+		let color = [r.min(a), g.min(a), b.min(a), a];
+		(0..4)
+			.map(|i| (i32::from(pixel[i]) - i32::from(color[i])).pow(2))
+			.sum::<i32>()
+	};
+
+	let mut text = String::new();
+	for row in pixmap.data().chunks_exact(pixmap.width() as usize * 4) {
+		for pixel in row.chunks_exact(4) {
+			let (c, _) = palette
+				.iter()
+				.chain([&transparent])
+				.min_by_key(|(_, color)| distance(pixel, *color))
+				.unwrap();
+			text.push(*c);
+		}
+		text.push('\n');
+	}
+	text
+}
+
+#[cfg(test)]
+mod tests {
+	use egui::{Color32, ColorImage, LayerId, Rect, TextureOptions};
+
+	use super::*;
+
+	fn painter(ctx: &egui::Context) -> egui::Painter {
+		ctx.layer_painter(LayerId::background())
+	}
+
+	fn rect(min: (f32, f32), max: (f32, f32)) -> Rect {
+		Rect::from_min_max(Pos2::new(min.0, min.1), Pos2::new(max.0, max.1))
+	}
+
+	fn pixel(pixmap: &Pixmap, x: u32, y: u32) -> [u8; 4] {
+		let i = ((y * pixmap.width() + x) * 4) as usize;
+		pixmap.data()[i..i + 4].try_into().unwrap()
+	}
+
+	/// Draws the pixmap coverage as text, `#` for opaque, `+` for partially
+	/// covered and `.` for transparent pixels.
+	fn coverage(pixmap: &Pixmap) -> String {
+		let mut text = String::new();
+		for row in pixmap.data().chunks_exact(pixmap.width() as usize * 4) {
+			for pixel in row.chunks_exact(4) {
+				text.push(match pixel[3] {
+					0 => '.',
+					255 => '#',
+					_ => '+',
+				});
+			}
+			text.push('\n');
+		}
+		text
+	}
+
+	#[test]
+	fn filled_rect() {
+		let pixmap = render_offscreen(8, 6, 1.0, |ctx| {
+			painter(ctx).rect_filled(rect((2.0, 1.0), (6.0, 4.0)), 0.0, Color32::RED);
+		});
+
+		assert_eq!(
+			coverage(&pixmap),
+			"\
+........
+..####..
+..####..
+..####..
+........
+........
+"
+		);
+		assert_eq!(pixel(&pixmap, 2, 1), [255, 0, 0, 255]);
+		assert_eq!(pixel(&pixmap, 5, 3), [255, 0, 0, 255]);
+	}
+
+	#[test]
+	fn scales_to_pixels_per_point() {
+		let pixmap = render_offscreen(8, 6, 2.0, |ctx| {
+			painter(ctx).rect_filled(rect((1.0, 1.0), (3.0, 2.0)), 0.0, Color32::BLUE);
+		});
+
+		assert_eq!(
+			coverage(&pixmap),
+			"\
+........
+........
+..####..
+..####..
+........
+........
+"
+		);
+		assert_eq!(pixel(&pixmap, 3, 3), [0, 0, 255, 255]);
+	}
+
+	#[test]
+	fn clips_to_clip_rect() {
+		let pixmap = render_offscreen(8, 4, 1.0, |ctx| {
+			painter(ctx)
+				.with_clip_rect(rect((0.0, 0.0), (5.0, 4.0)))
+				.rect_filled(rect((1.0, 1.0), (7.0, 3.0)), 0.0, Color32::GREEN);
+		});
+
+		assert_eq!(
+			coverage(&pixmap),
+			"\
+........
+.####...
+.####...
+........
+"
+		);
+	}
+
+	#[test]
+	fn blends_premultiplied() {
+		let pixmap = render_offscreen(4, 4, 1.0, |ctx| {
+			let painter = painter(ctx);
+			painter.rect_filled(rect((0.0, 0.0), (4.0, 4.0)), 0.0, Color32::BLUE);
+			painter.rect_filled(
+				rect((0.0, 0.0), (2.0, 4.0)),
+				0.0,
+				Color32::from_rgba_unmultiplied(255, 0, 0, 128),
+			);
+		});
+
+		// egui premultiplies in gamma space, 255 at half alpha becomes 188
+		assert_eq!(pixel(&pixmap, 0, 0), [188, 0, 127, 255]);
+		assert_eq!(pixel(&pixmap, 3, 0), [0, 0, 255, 255]);
+	}
+
+	#[test]
+	fn feathers_edges() {
+		let pixmap = render_offscreen(12, 12, 1.0, |ctx| {
+			painter(ctx).circle_filled(Pos2::new(6.0, 6.0), 4.0, Color32::WHITE);
+		});
+
+		assert_eq!(
+			coverage(&pixmap),
+			"\
+............
+.....++.....
+...++##++...
+..++####++..
+..+######+..
+.+########+.
+.+########+.
+..+######+..
+..++####++..
+...++##++...
+.....++.....
+............
+"
+		);
+
+		// Premultiplied colors never exceed their alpha
+		for pixel in pixmap.data().chunks_exact(4) {
+			assert!(pixel[..3].iter().all(|&c| c <= pixel[3]), "{pixel:?}");
+		}
+	}
+
+	#[test]
+	fn samples_textures() {
+		let image = ColorImage {
+			size: [2, 2],
+			pixels: vec![Color32::RED, Color32::GREEN, Color32::BLUE, Color32::WHITE],
+		};
+
+		let mut texture = None;
+		let pixmap = render_offscreen(4, 4, 1.0, |ctx| {
+			let texture = texture.get_or_insert_with(|| {
+				ctx.load_texture("test", image.clone(), TextureOptions::NEAREST)
+			});
+			painter(ctx).image(
+				texture.id(),
+				rect((0.0, 0.0), (4.0, 4.0)),
+				rect((0.0, 0.0), (1.0, 1.0)),
+				Color32::WHITE,
+			);
+		});
+
+		assert_eq!(pixel(&pixmap, 0, 0), [255, 0, 0, 255]);
+		assert_eq!(pixel(&pixmap, 3, 1), [0, 255, 0, 255]);
+		assert_eq!(pixel(&pixmap, 1, 2), [0, 0, 255, 255]);
+		assert_eq!(pixel(&pixmap, 2, 3), [255, 255, 255, 255]);
+	}
+
+	#[test]
+	fn renders_text() {
+		let mut text_rect = Rect::NOTHING;
+		let pixmap = render_offscreen(64, 32, 1.0, |ctx| {
+			text_rect = painter(ctx).text(
+				Pos2::new(4.0, 4.0),
+				egui::Align2::LEFT_TOP,
+				"Hi",
+				egui::FontId::proportional(16.0),
+				Color32::WHITE,
+			);
+		});
+
+		let mut covered = 0;
+		for y in 0..pixmap.height() {
+			for x in 0..pixmap.width() {
+				if pixel(&pixmap, x, y)[3] == 0 {
+					continue;
+				}
+
+				covered += 1;
+				let center = Pos2::new(x as f32 + 0.5, y as f32 + 0.5);
+				assert!(
+					text_rect.expand(1.0).contains(center),
+					"{x},{y} outside text"
+				);
+			}
+		}
+		assert!(covered > 20, "only {covered} pixels of text were drawn");
+	}
+
+	#[test]
+	fn partial_texture_update() {
+		let id = TextureId::User(0);
+		let full = ImageDelta::full(
+			ColorImage::new([2, 2], Color32::RED),
+			TextureOptions::NEAREST,
+		);
+		let partial = ImageDelta::partial(
+			[1, 0],
+			ColorImage::new([1, 2], Color32::BLUE),
+			TextureOptions::NEAREST,
+		);
+
+		let mut rasterizer = Rasterizer::default();
+		rasterizer.update_texture(id, &full);
+		rasterizer.update_texture(id, &partial);
+
+		let texture = &rasterizer.textures[&id];
+		assert_eq!(
+			texture.pixels,
+			[Color32::RED, Color32::BLUE, Color32::RED, Color32::BLUE]
+		);
+		assert_eq!(
+			texture.sample(Pos2::new(0.75, 0.75)),
+			[0.0, 0.0, 255.0, 255.0]
+		);
+	}
+}
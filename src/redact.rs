@@ -0,0 +1,144 @@
+use std::path::Path;
+
+use regex::Regex;
+
+/// Replaces the home directory, the logs directory and the user name in
+/// diagnostics files.
+pub struct Redactor {
+	patterns: Vec<(Regex, &'static str)>,
+}
+
+impl Redactor {
+	pub fn new(home: Option<&Path>, logs_dir: Option<&Path>, user: Option<&str>) -> Self {
+		let mut patterns = Vec::new();
+
+		// A logs directory in the home directory is redacted with it,
+		// compared as text as Windows paths are case-insensitive
+		let lowercase = |path: &Path| path.to_string_lossy().to_lowercase();
+		let in_home = |dir: &Path| home.is_some_and(|h| lowercase(dir).starts_with(&lowercase(h)));
+		if let Some(logs_dir) = logs_dir.filter(|dir| !in_home(dir)) {
+			patterns.extend(path_patterns(logs_dir, "<logs>"));
+		}
+
+		if let Some(home) = home {
+			patterns.extend(path_patterns(home, "~"));
+		}
+
+		// Short user names would redact unrelated text
+		if let Some(user) = user.filter(|user| user.chars().count() >= 3) {
+			let is_word = |c: char| c.is_alphanumeric() || c == '_';
+
+			// Word boundaries only match next to word characters
+			let start = if user.starts_with(is_word) { r"\b" } else { "" };
+			let end = if user.ends_with(is_word) { r"\b" } else { "" };
+
+			let pattern = format!("(?i){start}{}{end}", regex::escape(user));
+			if let Ok(regex) = Regex::new(&pattern) {
+				patterns.push((regex, "<user>"));
+			}
+		}
+
+		Self { patterns }
+	}
+
+	pub fn redact(&self, text: &str) -> String {
+		let mut text = text.to_string();
+		for (regex, replacement) in &self.patterns {
+			text = regex.replace_all(&text, *replacement).into_owned();
+		}
+		text
+	}
+}
+
+/// Patterns matching the path case-insensitively, with either separator,
+/// and escaped as in JSON.
+fn path_patterns(path: &Path, replacement: &'static str) -> Vec<(Regex, &'static str)> {
+	let path = path.to_string_lossy().into_owned();
+
+	let variants = [
+		path.clone(),
+		path.replace('\\', "/"),
+		path.replace('\\', "\\\\"),
+	];
+
+	variants
+		.iter()
+		.filter_map(|variant| Regex::new(&format!("(?i){}", regex::escape(variant))).ok())
+		.map(|regex| (regex, replacement))
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn redactor(logs_dir: Option<&str>, user: Option<&str>) -> Redactor {
+		Redactor::new(
+			Some(Path::new(r"C:\Users\Amr")),
+			logs_dir.map(Path::new),
+			user,
+		)
+	}
+
+	#[test]
+	fn redacts_home() {
+		let redactor = redactor(None, None);
+		assert_eq!(
+			redactor.redact(r"C:\Users\Amr\.config\komorebi-switcher.toml"),
+			r"~\.config\komorebi-switcher.toml"
+		);
+		assert_eq!(
+			redactor.redact("c:/users/amr/.config/komorebi-switcher.toml"),
+			"~/.config/komorebi-switcher.toml"
+		);
+		assert_eq!(
+			redactor.redact(r#"{"path":"C:\\Users\\Amr\\komorebi.json"}"#),
+			r#"{"path":"~\\komorebi.json"}"#
+		);
+	}
+
+	#[test]
+	fn redacts_logs_dir_outside_home() {
+		let redactor = redactor(Some(r"D:\Logs\amr"), None);
+		assert_eq!(
+			redactor.redact(r"D:\Logs\amr\komorebi-switcher.log"),
+			r"<logs>\komorebi-switcher.log"
+		);
+	}
+
+	#[test]
+	fn redacts_logs_dir_in_home_as_home() {
+		let redactor = redactor(
+			Some(r"C:\Users\Amr\AppData\Roaming\komorebi-switcher\logs"),
+			None,
+		);
+		assert_eq!(
+			redactor.redact(r"C:\Users\Amr\AppData\Roaming\komorebi-switcher\logs"),
+			r"~\AppData\Roaming\komorebi-switcher\logs"
+		);
+	}
+
+	#[test]
+	fn redacts_user_name_as_a_word() {
+		let redactor = redactor(None, Some("amr"));
+		assert_eq!(redactor.redact("Logged in as AMR."), "Logged in as <user>.");
+		assert_eq!(redactor.redact("DESKTOP-amr"), "DESKTOP-<user>");
+		assert_eq!(
+			redactor.redact("Samrat is not the user, nor is amr_1"),
+			"Samrat is not the user, nor is amr_1"
+		);
+	}
+
+	#[test]
+	fn redacts_user_name_with_symbols() {
+		let redactor = redactor(None, Some("amr."));
+		assert_eq!(redactor.redact("user amr. here"), "user <user> here");
+		assert_eq!(redactor.redact("samr. here"), "samr. here");
+	}
+
+	#[test]
+	fn ignores_short_user_names() {
+		let redactor = redactor(None, Some("am"));
+		assert_eq!(redactor.redact("am I redacted"), "am I redacted");
+	}
+}
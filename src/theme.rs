@@ -0,0 +1,158 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+
+/// Name of the default theme, which uses the system colors.
+pub const DEFAULT_THEME: &str = "default";
+
+/// Built-in themes, embedded at compile time.
+const BUILTIN_THEMES: [(&str, &str); 3] = [
+	(
+		"catppuccin",
+		include_str!("../assets/themes/catppuccin.toml"),
+	),
+	("nord", include_str!("../assets/themes/nord.toml")),
+	("gruvbox", include_str!("../assets/themes/gruvbox.toml")),
+];
+
+/// Colors of the switcher, unset colors fall back to the system colors.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct ThemeColors {
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub text: Option<String>,
+	/// Text color of workspaces that are neither focused nor hovered.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub inactive_text: Option<String>,
+	/// Text color of empty workspaces that are neither focused nor hovered.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub empty_text: Option<String>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub hover_background: Option<String>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub focused_background: Option<String>,
+	/// Outline color of hovered and focused buttons.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub stroke: Option<String>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub active_indicator: Option<String>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub busy_indicator: Option<String>,
+}
+
+impl ThemeColors {
+	/// Merges two sets of colors, with `self` taking precedence over `other`.
+	pub fn merge(self, other: Self) -> Self {
+		Self {
+			text: self.text.or(other.text),
+			inactive_text: self.inactive_text.or(other.inactive_text),
+			empty_text: self.empty_text.or(other.empty_text),
+			hover_background: self.hover_background.or(other.hover_background),
+			focused_background: self.focused_background.or(other.focused_background),
+			stroke: self.stroke.or(other.stroke),
+			active_indicator: self.active_indicator.or(other.active_indicator),
+			busy_indicator: self.busy_indicator.or(other.busy_indicator),
+		}
+	}
+}
+
+/// A color theme, with variants for light and dark mode.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct Theme {
+	#[serde(default)]
+	pub light: ThemeColors,
+	#[serde(default)]
+	pub dark: ThemeColors,
+}
+
+impl Theme {
+	/// Gets the colors of the variant matching the system theme.
+	pub fn colors(&self, dark_mode: bool) -> &ThemeColors {
+		if dark_mode {
+			&self.dark
+		} else {
+			&self.light
+		}
+	}
+}
+
+/// Directory of user themes, each theme is a `<name>.toml` file.
+pub fn themes_dir() -> anyhow::Result<PathBuf> {
+	dirs::home_dir()
+		.context("Could not determine home directory")
+		.map(|dir| dir.join(".config").join("komorebi-switcher").join("themes"))
+}
+
+/// Gets the names of the built-in themes and the themes in [`themes_dir`].
+#[cfg(windows)]
+pub fn available_themes() -> Vec<String> {
+	let mut themes = vec![DEFAULT_THEME.to_string()];
+	themes.extend(BUILTIN_THEMES.iter().map(|(name, _)| name.to_string()));
+
+	let entries = themes_dir()
+		.ok()
+		.and_then(|dir| std::fs::read_dir(dir).ok());
+	for entry in entries.into_iter().flatten().flatten() {
+		let path = entry.path();
+		if path.extension().is_some_and(|ext| ext == "toml") {
+			if let Some(name) = path.file_stem().and_then(|s| s.to_str()) {
+				if !themes.iter().any(|t| t == name) {
+					themes.push(name.to_string());
+				}
+			}
+		}
+	}
+
+	themes
+}
+
+thread_local! {
+	static CACHE: RefCell<HashMap<String, Theme>> = RefCell::new(HashMap::new());
+}
+
+/// Gets a theme by name, built-in themes take precedence over user themes.
+///
+/// Themes are cached after the first load, invalid or missing themes
+/// log a warning and fall back to the default theme.
+pub fn get_theme(name: &str) -> Theme {
+	CACHE.with_borrow_mut(|cache| {
+		cache
+			.entry(name.to_string())
+			.or_insert_with(|| {
+				load_theme(name).unwrap_or_else(|e| {
+					tracing::warn!("Failed to load theme '{name}', using default instead: {e}");
+					crate::notifications::warn(format!(
+						"Failed to load theme '{name}', using default instead: {e}"
+					));
+					Theme::default()
+				})
+			})
+			.clone()
+	})
+}
+
+/// Clears the themes cache, so user themes are loaded again from disk.
+#[cfg(windows)]
+pub fn clear_cache() {
+	CACHE.with_borrow_mut(|cache| cache.clear());
+}
+
+fn load_theme(name: &str) -> anyhow::Result<Theme> {
+	if name == DEFAULT_THEME {
+		return Ok(Theme::default());
+	}
+
+	if let Some((_, content)) = BUILTIN_THEMES.iter().find(|(n, _)| *n == name) {
+		return toml::from_str(content).context("Invalid built-in theme");
+	}
+
+	let path = themes_dir()?.join(format!("{name}.toml"));
+
+	tracing::info!("Loading theme from {}", path.display());
+
+	let content = std::fs::read_to_string(&path)
+		.with_context(|| format!("Failed to read {}", path.display()))?;
+	toml::from_str(&content).with_context(|| format!("Invalid theme file {}", path.display()))
+}
@@ -1,5 +1,7 @@
 use std::fmt::Display;
+use std::path::PathBuf;
 
+use anyhow::Context;
 use font_kit::family_name::FamilyName;
 use font_kit::properties::{Properties, Weight};
 use font_kit::source::SystemSource;
@@ -17,6 +19,17 @@ pub fn find_font(family: &str, weight: u16) -> Option<font_kit::font::Font> {
 	handle.load().ok()
 }
 
+/// Gets the default directory log files are written to.
+pub fn logs_dir() -> anyhow::Result<PathBuf> {
+	let logs_dir = dirs::data_dir()
+		.context("Failed to get $data_dir path")?
+		.join("komorebi-switcher")
+		.join("logs");
+	Ok(logs_dir)
+}
+
+/// Shows a blocking error dialog, only used for fatal errors,
+/// see [`crate::notifications`] for the others.
 pub fn error_dialog<T: Display>(error: T) {
 	rfd::MessageDialog::new()
 		.set_title("komorebi-switcher")
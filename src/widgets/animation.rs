@@ -0,0 +1,80 @@
+use crate::config::{AnimationsConfig, Easing};
+
+/// Resolved animation settings of the switcher buttons.
+#[derive(Debug, Clone, Copy)]
+pub struct ButtonAnimation {
+	/// Whether animations are enabled, disabled when reducing motion.
+	pub enabled: bool,
+	pub sliding_indicator: bool,
+	/// Duration of all animations in seconds, unset uses the default duration
+	/// of each animation.
+	pub duration: Option<f32>,
+	pub easing: fn(f32) -> f32,
+}
+
+impl Default for ButtonAnimation {
+	fn default() -> Self {
+		Self {
+			enabled: true,
+			sliding_indicator: false,
+			duration: None,
+			easing: egui_animation::easing::sine_out,
+		}
+	}
+}
+
+impl ButtonAnimation {
+	/// Resolves the animation settings from the config,
+	/// `system_enabled` is the OS animations preference.
+	pub fn new(config: &AnimationsConfig, system_enabled: bool) -> Self {
+		let enabled = system_enabled && !config.reduce_motion;
+
+		Self {
+			enabled,
+			sliding_indicator: enabled && config.sliding_indicator,
+			duration: config.duration.map(|d| d as f32 / 1000.0),
+			easing: easing_fn(config.easing),
+		}
+	}
+
+	/// Animates the value with the given id towards the target value,
+	/// returns the target value right away if animations are disabled.
+	pub fn animate(
+		&self,
+		ctx: &egui::Context,
+		id: impl std::hash::Hash + Sized,
+		target: f32,
+		default_duration: f32,
+	) -> f32 {
+		let duration = self.duration.unwrap_or(default_duration);
+		if !self.enabled || duration <= 0.0 {
+			return target;
+		}
+
+		let value = egui_animation::animate_eased(ctx, id, target, duration, self.easing);
+
+		if value != target {
+			ctx.request_repaint();
+		}
+
+		value
+	}
+}
+
+fn easing_fn(easing: Easing) -> fn(f32) -> f32 {
+	use egui_animation::easing::*;
+
+	match easing {
+		Easing::Linear => linear,
+		Easing::SineOut => sine_out,
+		Easing::SineInOut => sine_in_out,
+		Easing::QuadOut => quad_out,
+		Easing::QuadInOut => quad_in_out,
+		Easing::CubicOut => cubic_out,
+		Easing::CubicInOut => cubic_in_out,
+		Easing::ExpoOut => expo_out,
+		Easing::BackOut => back_out,
+		Easing::ElasticOut => elastic_out,
+		Easing::BounceOut => bounce_out,
+	}
+}
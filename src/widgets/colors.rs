@@ -0,0 +1,72 @@
+use crate::color_expr::{resolve_color, SystemColors};
+use crate::config::ColorsConfig;
+
+/// Resolved colors of the switcher buttons,
+/// unset colors fall back to the system colors.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ButtonColors {
+	pub inactive_text: Option<egui::Color32>,
+	pub empty_text: Option<egui::Color32>,
+	pub hover_background: Option<egui::Color32>,
+	pub focused_background: Option<egui::Color32>,
+	pub stroke: Option<egui::Color32>,
+}
+
+impl ButtonColors {
+	/// Resolves the theme colors against the system colors,
+	/// invalid colors are ignored.
+	pub fn new(colors: &ColorsConfig, system: &SystemColors) -> Self {
+		let parse = |color: &Option<String>| {
+			let color = color.as_deref()?;
+			egui_color_from_color(color, system)
+		};
+
+		Self {
+			inactive_text: parse(&colors.inactive_text),
+			empty_text: parse(&colors.empty_text),
+			hover_background: parse(&colors.hover_background),
+			focused_background: parse(&colors.focused_background),
+			stroke: parse(&colors.stroke),
+		}
+	}
+
+	pub(super) fn background(&self, dark_mode: bool, hovered: bool) -> egui::Color32 {
+		let color = if hovered {
+			self.hover_background
+		} else {
+			self.focused_background
+		};
+
+		color.unwrap_or(if dark_mode {
+			egui::Color32::from_rgba_unmultiplied(255, 255, 255, 1)
+		} else {
+			egui::Color32::from_rgba_unmultiplied(255, 255, 255, 30)
+		})
+	}
+
+	pub(super) fn stroke(&self, dark_mode: bool) -> egui::Color32 {
+		self.stroke.unwrap_or(if dark_mode {
+			egui::Color32::from_rgba_unmultiplied(255, 255, 255, 2)
+		} else {
+			egui::Color32::from_rgba_unmultiplied(33, 33, 33, 33)
+		})
+	}
+
+	pub(super) fn inactive_text(&self, text_color: egui::Color32, empty: bool) -> egui::Color32 {
+		let color = if empty {
+			self.empty_text.or(self.inactive_text)
+		} else {
+			self.inactive_text
+		};
+
+		color.unwrap_or_else(|| text_color.gamma_multiply(0.75))
+	}
+}
+
+/// Resolves a color expression into an egui color, see [`crate::color_expr::ColorExpr`].
+pub fn egui_color_from_color(color: &str, system: &SystemColors) -> Option<egui::Color32> {
+	let rgba = resolve_color(color, system)?.to_rgba8();
+	Some(egui::Color32::from_rgba_unmultiplied(
+		rgba.r, rgba.g, rgba.b, rgba.a,
+	))
+}
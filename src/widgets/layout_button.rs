@@ -1,7 +1,11 @@
+use crate::widgets::{ButtonColors, ButtonMetrics};
+
 pub struct LayoutButton<'a> {
 	text: &'a str,
 	text_color: Option<egui::Color32>,
 	dark_mode: Option<bool>,
+	metrics: ButtonMetrics,
+	colors: ButtonColors,
 }
 
 impl<'a> LayoutButton<'a> {
@@ -10,6 +14,8 @@ impl<'a> LayoutButton<'a> {
 			text,
 			text_color: None,
 			dark_mode: None,
+			metrics: ButtonMetrics::default(),
+			colors: ButtonColors::default(),
 		}
 	}
 
@@ -18,6 +24,16 @@ impl<'a> LayoutButton<'a> {
 		self
 	}
 
+	pub fn metrics(mut self, metrics: ButtonMetrics) -> Self {
+		self.metrics = metrics;
+		self
+	}
+
+	pub fn colors(mut self, colors: ButtonColors) -> Self {
+		self.colors = colors;
+		self
+	}
+
 	// pub fn text_color(mut self, olor: egui::Color32) -> Self {
 	//     self.text_color.replace(color);
 	//     self
@@ -31,13 +47,11 @@ impl<'a> LayoutButton<'a> {
 
 impl<'a> egui::Widget for LayoutButton<'a> {
 	fn ui(self, ui: &mut egui::Ui) -> egui::Response {
-		const RADIUS: f32 = 4.0;
-		const MIN_SIZE: egui::Vec2 = egui::vec2(28.0, 28.0);
-		const TEXT_PADDING: egui::Vec2 = egui::vec2(16.0, 8.0);
+		let metrics = self.metrics;
 
 		let dark_mode = self.dark_mode.unwrap_or_else(|| ui.visuals().dark_mode);
 
-		let font_id = egui::FontId::default();
+		let font_id = metrics.font_id();
 		let text_color = self.text_color.unwrap_or(if dark_mode {
 			egui::Color32::WHITE
 		} else {
@@ -49,7 +63,9 @@ impl<'a> egui::Widget for LayoutButton<'a> {
 			.painter()
 			.layout_no_wrap(text, font_id.clone(), text_color);
 
-		let size = MIN_SIZE.max(text_galley.rect.size() + TEXT_PADDING);
+		let size = metrics
+			.min_size
+			.max(text_galley.rect.size() + metrics.padding);
 
 		let (rect, response) = ui.allocate_at_least(size, egui::Sense::CLICK | egui::Sense::HOVER);
 
@@ -57,31 +73,28 @@ impl<'a> egui::Widget for LayoutButton<'a> {
 
 		// draw background
 		if response.hovered() {
-			let color = if dark_mode {
-				egui::Color32::from_rgba_unmultiplied(255, 255, 255, 1)
-			} else {
-				egui::Color32::from_rgba_unmultiplied(255, 255, 255, 30)
-			};
-
-			let stroke_color = if dark_mode {
-				egui::Color32::from_rgba_unmultiplied(255, 255, 255, 2)
-			} else {
-				egui::Color32::from_rgba_unmultiplied(33, 33, 33, 33)
-			};
+			let color = self.colors.background(dark_mode, response.hovered());
+			let stroke_color = self.colors.stroke(dark_mode);
 
 			let stroke = egui::Stroke {
 				width: 1.0,
 				color: stroke_color,
 			};
 
-			painter.rect(rect, RADIUS, color, stroke, egui::StrokeKind::Inside);
+			painter.rect(
+				rect,
+				metrics.radius,
+				color,
+				stroke,
+				egui::StrokeKind::Inside,
+			);
 		}
 
 		// draw text
 		let text_color = if response.hovered() {
 			text_color
 		} else {
-			text_color.gamma_multiply(0.75)
+			self.colors.inactive_text(text_color, false)
 		};
 
 		painter.text(
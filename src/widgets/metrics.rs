@@ -0,0 +1,59 @@
+use crate::config::MetricsConfig;
+
+/// Resolved sizes of the switcher buttons, in points.
+#[derive(Debug, Clone, Copy)]
+pub struct ButtonMetrics {
+	pub font_size: f32,
+	pub min_size: egui::Vec2,
+	pub padding: egui::Vec2,
+	pub radius: f32,
+	pub indicator_active_width: f32,
+	pub indicator_base_width: f32,
+	pub indicator_height: f32,
+}
+
+impl Default for ButtonMetrics {
+	fn default() -> Self {
+		Self {
+			font_size: egui::FontId::default().size,
+			min_size: egui::vec2(28.0, 28.0),
+			padding: egui::vec2(16.0, 8.0),
+			radius: 4.0,
+			indicator_active_width: 14.0,
+			indicator_base_width: 6.0,
+			indicator_height: 3.5,
+		}
+	}
+}
+
+impl ButtonMetrics {
+	/// Resolves the metrics from the config, falling back to the defaults
+	/// for unset values.
+	pub fn new(font_size: Option<f32>, metrics: &MetricsConfig) -> Self {
+		let default = Self::default();
+
+		Self {
+			font_size: font_size.unwrap_or(default.font_size),
+			min_size: egui::vec2(
+				metrics.min_width.unwrap_or(default.min_size.x),
+				metrics.min_height.unwrap_or(default.min_size.y),
+			),
+			padding: egui::vec2(
+				metrics.padding_x.unwrap_or(default.padding.x),
+				metrics.padding_y.unwrap_or(default.padding.y),
+			),
+			radius: metrics.corner_radius.unwrap_or(default.radius),
+			indicator_active_width: metrics
+				.indicator_active_width
+				.unwrap_or(default.indicator_active_width),
+			indicator_base_width: metrics
+				.indicator_width
+				.unwrap_or(default.indicator_base_width),
+			indicator_height: metrics.indicator_height.unwrap_or(default.indicator_height),
+		}
+	}
+
+	pub fn font_id(&self) -> egui::FontId {
+		egui::FontId::proportional(self.font_size)
+	}
+}
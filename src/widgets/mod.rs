@@ -0,0 +1,11 @@
+mod animation;
+mod colors;
+mod layout_button;
+mod metrics;
+mod workspace_button;
+
+pub use animation::*;
+pub use colors::*;
+pub use layout_button::*;
+pub use metrics::*;
+pub use workspace_button::*;
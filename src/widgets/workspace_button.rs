@@ -0,0 +1,515 @@
+use crate::config::IndicatorStyle;
+use crate::komorebi::Workspace;
+use crate::widgets::{ButtonAnimation, ButtonColors, ButtonMetrics};
+
+pub struct WorkspaceButton<'a> {
+	workspace: &'a Workspace,
+	label: Option<String>,
+	text_color: Option<egui::Color32>,
+	line_active_color: Option<egui::Color32>,
+	line_busy_color: Option<egui::Color32>,
+	indicator_opacity: f32,
+	indicator_style: IndicatorStyle,
+	dark_mode: Option<bool>,
+	metrics: ButtonMetrics,
+	colors: ButtonColors,
+	animation: ButtonAnimation,
+}
+
+impl<'a> WorkspaceButton<'a> {
+	pub fn new(workspace: &'a Workspace) -> Self {
+		Self {
+			workspace,
+			label: None,
+			text_color: None,
+			line_active_color: None,
+			line_busy_color: None,
+			indicator_opacity: 1.0,
+			indicator_style: IndicatorStyle::default(),
+			dark_mode: None,
+			metrics: ButtonMetrics::default(),
+			colors: ButtonColors::default(),
+			animation: ButtonAnimation::default(),
+		}
+	}
+
+	/// Sets the text drawn on the button, defaults to the workspace name.
+	pub fn label(mut self, label: impl Into<String>) -> Self {
+		self.label = Some(label.into());
+		self
+	}
+
+	pub fn dark_mode(mut self, dark_mode: Option<bool>) -> Self {
+		self.dark_mode = dark_mode;
+		self
+	}
+
+	pub fn metrics(mut self, metrics: ButtonMetrics) -> Self {
+		self.metrics = metrics;
+		self
+	}
+
+	pub fn colors(mut self, colors: ButtonColors) -> Self {
+		self.colors = colors;
+		self
+	}
+
+	pub fn animation(mut self, animation: ButtonAnimation) -> Self {
+		self.animation = animation;
+		self
+	}
+
+	pub fn text_color_opt(mut self, color: Option<egui::Color32>) -> Self {
+		self.text_color = color;
+		self
+	}
+
+	pub fn line_active_color_opt(mut self, color: Option<egui::Color32>) -> Self {
+		self.line_active_color = color;
+		self
+	}
+
+	pub fn line_busy_color_opt(mut self, color: Option<egui::Color32>) -> Self {
+		self.line_busy_color = color;
+		self
+	}
+
+	pub fn indicator_style(mut self, style: IndicatorStyle) -> Self {
+		self.indicator_style = style;
+		self
+	}
+
+	/// Multiplies the opacity of the indicator, used to dim the switcher
+	/// when its monitor is not focused.
+	pub fn indicator_opacity(mut self, opacity: f32) -> Self {
+		self.indicator_opacity = opacity.clamp(0.0, 1.0);
+		self
+	}
+}
+
+impl egui::Widget for WorkspaceButton<'_> {
+	fn ui(self, ui: &mut egui::Ui) -> egui::Response {
+		let metrics = self.metrics;
+
+		let dark_mode = self.dark_mode.unwrap_or_else(|| ui.visuals().dark_mode);
+
+		let font_id = metrics.font_id();
+		let text_color = self.text_color.unwrap_or(if dark_mode {
+			egui::Color32::WHITE
+		} else {
+			egui::Color32::BLACK
+		});
+
+		let text = self.label.unwrap_or_else(|| self.workspace.name.clone());
+		let text_galley = ui
+			.painter()
+			.layout_no_wrap(text.clone(), font_id.clone(), text_color);
+
+		let size = metrics
+			.min_size
+			.max(text_galley.rect.size() + metrics.padding);
+
+		let (rect, response) = ui.allocate_at_least(size, egui::Sense::CLICK | egui::Sense::HOVER);
+
+		let painter = ui.painter();
+
+		// draw background
+		if response.hovered() || self.workspace.focused {
+			let color = self.colors.background(dark_mode, response.hovered());
+			let stroke_color = self.colors.stroke(dark_mode);
+
+			let stroke = egui::Stroke {
+				width: 1.0,
+				color: stroke_color,
+			};
+
+			painter.rect(
+				rect,
+				metrics.radius,
+				color,
+				stroke,
+				egui::StrokeKind::Inside,
+			);
+		}
+
+		// draw indicator
+
+		// animate opacity
+		let target_opacity = (self.workspace.focused || !self.workspace.is_empty) as i32 as f32;
+		let opacity = self.animation.animate(
+			ui.ctx(),
+			format!("Opacity{}", self.workspace.index),
+			target_opacity,
+			0.3,
+		);
+
+		// animate width
+		let active_width = match self.indicator_style {
+			IndicatorStyle::Underline => rect.width(),
+			_ => metrics.indicator_active_width,
+		};
+		let target_line_width = if !response.is_pointer_button_down_on() && self.workspace.focused {
+			active_width
+		} else {
+			metrics.indicator_base_width
+		};
+		// when sliding, the width is not animated so the indicator
+		// of the previously focused workspace doesn't linger while shrinking
+		let line_width = if self.animation.sliding_indicator {
+			target_line_width
+		} else {
+			self.animation.animate(
+				ui.ctx(),
+				format!("Width{}", self.workspace.index),
+				target_line_width,
+				0.2,
+			)
+		};
+
+		// slide the active indicator from the previously focused workspace,
+		// all buttons of a switcher share the same animation ids
+		let indicator_rect = if self.animation.sliding_indicator && self.workspace.focused {
+			let min_x = self
+				.animation
+				.animate(ui.ctx(), "SlidingMinX", rect.min.x, 0.2);
+			let max_x = self
+				.animation
+				.animate(ui.ctx(), "SlidingMaxX", rect.max.x, 0.2);
+			rect.with_min_x(min_x).with_max_x(max_x)
+		} else {
+			rect
+		};
+
+		let opacity = opacity * self.indicator_opacity;
+
+		let color = if self.workspace.focused {
+			let c = self.line_active_color.unwrap_or(egui::Color32::CYAN);
+			egui::Color32::from_rgba_unmultiplied(
+				c.r(),
+				c.g(),
+				c.b(),
+				(opacity * f32::from(c.a())) as u8,
+			)
+		} else if let Some(c) = self.line_busy_color {
+			egui::Color32::from_rgba_unmultiplied(
+				c.r(),
+				c.g(),
+				c.b(),
+				(opacity * f32::from(c.a())) as u8,
+			)
+		} else if dark_mode {
+			egui::Color32::from_rgba_unmultiplied(180, 173, 170, (opacity * 125.0) as u8)
+		} else {
+			egui::Color32::from_rgba_unmultiplied(31, 31, 31, (opacity * 150.0) as u8)
+		};
+
+		let indicator_height = metrics.indicator_height;
+		let x = indicator_rect.center().x - line_width / 2.0;
+		let line_rect = indicator_rect.with_min_x(x).with_max_x(x + line_width);
+
+		match self.indicator_style {
+			IndicatorStyle::BottomLine | IndicatorStyle::Underline => {
+				let line_rect = line_rect.with_min_y(indicator_rect.max.y - indicator_height);
+				painter.rect_filled(line_rect, metrics.radius, color);
+			}
+			IndicatorStyle::TopLine => {
+				let line_rect = line_rect.with_max_y(indicator_rect.min.y + indicator_height);
+				painter.rect_filled(line_rect, metrics.radius, color);
+			}
+			IndicatorStyle::Pill => {
+				painter.rect_filled(indicator_rect, indicator_rect.height() / 2.0, color);
+			}
+			IndicatorStyle::Dot => {
+				let radius = metrics.indicator_base_width / 2.0;
+				let center = egui::pos2(
+					indicator_rect.center().x,
+					indicator_rect.max.y - radius - 1.0,
+				);
+				painter.circle_filled(center, radius, color);
+			}
+			IndicatorStyle::Box => {
+				let stroke = egui::Stroke::new((indicator_height / 2.0).max(1.0), color);
+				painter.rect_stroke(
+					indicator_rect,
+					metrics.radius,
+					stroke,
+					egui::StrokeKind::Inside,
+				);
+			}
+			IndicatorStyle::None => {}
+		}
+
+		// draw text
+		let text_color = if response.hovered() || self.workspace.focused {
+			text_color
+		} else {
+			self.colors
+				.inactive_text(text_color, self.workspace.is_empty)
+		};
+
+		painter.text(
+			rect.center(),
+			egui::Align2::CENTER_CENTER,
+			text,
+			font_id,
+			text_color,
+		);
+
+		response
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use egui::Color32;
+
+	use super::*;
+	use crate::color_expr::SystemColors;
+	use crate::config::{ColorsConfig, MetricsConfig};
+	use crate::rasterizer::{render_offscreen, snapshot};
+
+	const ACCENT: Color32 = Color32::from_rgb(0, 120, 215);
+
+	fn workspaces() -> [Workspace; 3] {
+		let workspace = |index: usize, focused: bool, is_empty: bool| Workspace {
+			name: (index + 1).to_string(),
+			index,
+			focused,
+			is_empty,
+			..Default::default()
+		};
+
+		[
+			workspace(0, true, false),
+			workspace(1, false, false),
+			workspace(2, false, true),
+		]
+	}
+
+	fn metrics() -> ButtonMetrics {
+		ButtonMetrics::new(
+			Some(11.0),
+			&MetricsConfig {
+				min_width: Some(16.0),
+				min_height: Some(16.0),
+				padding_x: Some(4.0),
+				padding_y: Some(4.0),
+				corner_radius: Some(2.0),
+				indicator_width: Some(4.0),
+				indicator_active_width: Some(8.0),
+				indicator_height: Some(2.0),
+				..Default::default()
+			},
+		)
+	}
+
+	/// Renders a row of focused, busy and empty workspace buttons.
+	fn render(dark_mode: bool, colors: ButtonColors, style: IndicatorStyle) -> tiny_skia::Pixmap {
+		let workspaces = workspaces();
+
+		render_offscreen(96, 36, 2.0, |ctx| {
+			egui::CentralPanel::default()
+				.frame(egui::Frame::NONE)
+				.show(ctx, |ui| {
+					ui.spacing_mut().item_spacing = egui::Vec2::ZERO;
+					ui.horizontal(|ui| {
+						for workspace in &workspaces {
+							ui.add(
+								WorkspaceButton::new(workspace)
+									.dark_mode(Some(dark_mode))
+									.metrics(metrics())
+									.colors(colors)
+									.animation(ButtonAnimation {
+										enabled: false,
+										..Default::default()
+									})
+									.line_active_color_opt(Some(ACCENT))
+									.indicator_style(style),
+							);
+						}
+					});
+				});
+		})
+	}
+
+	#[test]
+	fn dark_theme() {
+		let pixmap = render(true, ButtonColors::default(), IndicatorStyle::BottomLine);
+
+		let text = Color32::WHITE;
+		let palette = [
+			('#', text),
+			('+', text.gamma_multiply(0.75)),
+			('a', ACCENT),
+			('b', Color32::from_rgba_unmultiplied(180, 173, 170, 125)),
+			('f', Color32::from_rgba_unmultiplied(255, 255, 255, 1)),
+			('s', Color32::from_rgba_unmultiplied(255, 255, 255, 2)),
+		];
+
+		assert_eq!(
+			snapshot(&pixmap, &palette),
+			"\
+................................................................................................
+................................................................................................
+..ffssssssssssssssssssssssssff..................................................................
+.fssssssssssssssssssssssssssssf.................................................................
+fssffffffffffffffffffffffffffssf................................................................
+fsffffffffffffffffffffffffffffsf................................................................
+ssffffffffffffffffffffffffffffss................................................................
+ssffffffffffffffffffffffffffffss................................................................
+ssffffffffffffffffffffffffffffss................................................................
+ssffffffffffffffffffffffffffffss................................................................
+ssffffffffffffffbbffffffffffffss............sbb+bbs.........................sbb+bbs.............
+ssfffffffffffffb##ffffffffffffss...........s+++bb++b.......................s++bb+++s............
+ssfffffffffffsb###ffffffffffffss...........sbs...s++........................bs...b+b............
+ssffffffffffb##++#ffffffffffffss..................b+s.............................b+............
+ssffffffffffb+bf+#ffffffffffffss..................b+s.............................b+s...........
+ssffffffffffffff+#ffffffffffffss..................b+s............................s++............
+ssffffffffffffff+#ffffffffffffss.................s+b............................bb+b............
+ssffffffffffffff+#ffffffffffffss................s++s..........................b+++b.............
+ssffffffffffffff+#ffffffffffffss...............sb+b...........................bbb++b............
+ssffffffffffffff+#ffffffffffffss..............sb+b...............................s++s...........
+ssffffffffffffff+#ffffffffffffss.............sb+b.................................s+b...........
+ssffffffffffffff+#ffffffffffffss.............b+b...................................+b...........
+ssffffffffffffff+#ffffffffffffss............b+b...................................s+b...........
+ssffffffffffffff+#ffffffffffffss............++s...................................b+b...........
+ssffffffffffffff+#ffffffffffffss...........s+bbbbbbbb......................bbbssbb++s...........
+ssffffffffffffff+#ffffffffffffss...........b++++++++b......................b++++++bs............
+ssffffffffffffffffffffffffffffss............................................sbbbbs..............
+ssffffffffffffffffffffffffffffss................................................................
+ssffffffffffffffffffffffffffffss................................................................
+ssffffffffffffffffffffffffffffss................................................................
+ssffffffffffffffffffffffffffffss................................................................
+fsffffffsaaaaaaaaaaaaaasffffffsf............sbbbbbbs............................................
+fssfffffaaaaaaaaaaaaaaaafffffssf............bbbbbbbb............................................
+.fssssssaaaaaaaaaaaaaaaassssssf.............bbbbbbbb............................................
+..ffsssssaaaaaaaaaaaaaasssssff..............sbbbbbbs............................................
+................................................................................................
+"
+		);
+	}
+
+	#[test]
+	fn light_theme() {
+		let pixmap = render(false, ButtonColors::default(), IndicatorStyle::BottomLine);
+
+		let text = Color32::BLACK;
+		let palette = [
+			('#', text),
+			('+', text.gamma_multiply(0.75)),
+			('a', ACCENT),
+			('b', Color32::from_rgba_unmultiplied(31, 31, 31, 150)),
+			('f', Color32::from_rgba_unmultiplied(255, 255, 255, 30)),
+			('s', Color32::from_rgba_unmultiplied(33, 33, 33, 33)),
+		];
+
+		assert_eq!(
+			snapshot(&pixmap, &palette),
+			"\
+................................................................................................
+................................................................................................
+...ssssssssssssssssssssssssss...................................................................
+.ssssssssssssssssssssssssssssss.................................................................
+.ssffffffffffffffffffffffffffss.................................................................
+ssffffffffffffffffffffffffffffss................................................................
+ssffffffffffffffffffffffffffffss................................................................
+ssffffffffffffffffffffffffffffss................................................................
+ssffffffffffffffffffffffffffffss................................................................
+ssffffffffffffffffffffffffffffss................................................................
+ssffffffffffffffbbffffffffffffss............sbb++bs.........................sb++bbs.............
+ssfffffffffffffb##ffffffffffffss...........s+++bb++s.......................s++bb+++s............
+ssffffffffffffb###ffffffffffffss...........s+s...s++........................ss...s+b............
+ssffffffffffb##++#ffffffffffffss..................b+s.............................++............
+ssffffffffffb+bf+#ffffffffffffss..................b+s.............................b+............
+ssffffffffffffff+#ffffffffffffss..................b+.............................s++............
+ssffffffffffffff+#ffffffffffffss.................s+b............................s++s............
+ssffffffffffffff+#ffffffffffffss.................++s..........................b+++s.............
+ssffffffffffffff+#ffffffffffffss...............sb+s...........................sbb++s............
+ssffffffffffffff+#ffffffffffffss..............s++s...............................s++s...........
+ssffffffffffffff+#ffffffffffffss..............++s.................................s+b...........
+ssffffffffffffff+#ffffffffffffss.............b+s...................................++...........
+ssffffffffffffff+#ffffffffffffss............s+b....................................+b...........
+ssffffffffffffff+#ffffffffffffss............++....................................b+b...........
+ssffffffffffffff+#ffffffffffffss...........s+bsssssss......................sbssssb++............
+ssffffffffffffff+#ffffffffffffss...........s+++++++++......................s++++++bs............
+ssffffffffffffffffffffffffffffss.............................................sssss..............
+ssffffffffffffffffffffffffffffss................................................................
+ssffffffffffffffffffffffffffffss................................................................
+ssffffffffffffffffffffffffffffss................................................................
+ssffffffffffffffffffffffffffffss................................................................
+ssffffffbaaaaaaaaaaaaaabffffffss............sbbbbbbs............................................
+.ssfffffaaaaaaaaaaaaaaaafffffss.............bbbbbbbb............................................
+.sssssssaaaaaaaaaaaaaaaasssssss.............bbbbbbbb............................................
+...sssssbaaaaaaaaaaaaaabsssss...............sbbbbbbs............................................
+................................................................................................
+"
+		);
+	}
+
+	#[test]
+	fn configured_colors() {
+		let colors = ButtonColors::new(
+			&ColorsConfig {
+				inactive_text: Some("#ff0000".into()),
+				empty_text: Some("#00ff00".into()),
+				focused_background: Some("#0000ff".into()),
+				stroke: Some("#ffff00".into()),
+				..Default::default()
+			},
+			&SystemColors::default(),
+		);
+		let pixmap = render(true, colors, IndicatorStyle::TopLine);
+
+		let palette = [
+			('#', Color32::WHITE),
+			('r', Color32::RED),
+			('g', Color32::GREEN),
+			('a', ACCENT),
+			('b', Color32::from_rgba_unmultiplied(180, 173, 170, 125)),
+			('f', Color32::BLUE),
+			('s', Color32::YELLOW),
+		];
+
+		assert_eq!(
+			snapshot(&pixmap, &palette),
+			"\
+................................................................................................
+................................................................................................
+..bssssssaaaaaaaaaaaaaassssssb...............bbbbbb.............................................
+.bssssssaaaaaaaaaaaaaaaassssssb.............bbbbbbbb............................................
+bssaffffaaaaaaaaaaaaaaaaffffassb............bbbbbbbb............................................
+ssaffffffaaaaaaaaaaaaaaffffffass.............bbbbbb.............................................
+ssffffffffffffffffffffffffffffss................................................................
+ssffffffffffffffffffffffffffffss................................................................
+ssffffffffffffffffffffffffffffss................................................................
+ssffffffffffffffffffffffffffffss................................................................
+ssffffffffffffffa#ffffffffffffss.............rrrrr...........................ggggg..............
+ssfffffffffffffa##ffffffffffffss............rrrrrrr.........................ggggggg.............
+ssffffffffffffa###ffffffffffffss............r.....rr..............................gg............
+ssffffffffffa#####ffffffffffffss..................rr..............................gg............
+ssffffffffffa#af##ffffffffffffss..................rr..............................gg............
+ssffffffffffffff##ffffffffffffss..................rr..............................gg............
+ssffffffffffffff##ffffffffffffss..................rr.............................gg.............
+ssffffffffffffff##ffffffffffffss.................rr...........................gggg..............
+ssffffffffffffff##ffffffffffffss................rr.............................gggg.............
+ssffffffffffffff##ffffffffffffss...............rr.................................gg............
+ssffffffffffffff##ffffffffffffss..............rr...................................gg...........
+ssffffffffffffff##ffffffffffffss.............rr....................................gg...........
+ssffffffffffffff##ffffffffffffss.............rr....................................gg...........
+ssffffffffffffff##ffffffffffffss............rr....................................ggg...........
+ssffffffffffffff##ffffffffffffss............rr..............................g....ggg............
+ssffffffffffffff##ffffffffffffss............rrrrrrrrr......................bggggggg.............
+ssffffffffffffffffffffffffffffss..............................................bb................
+ssffffffffffffffffffffffffffffss................................................................
+ssffffffffffffffffffffffffffffss................................................................
+ssffffffffffffffffffffffffffffss................................................................
+ssffffffffffffffffffffffffffffss................................................................
+ssaffffffffffffffffffffffffffass................................................................
+bssaffffffffffffffffffffffffassb................................................................
+.bssssssssssssssssssssssssssssb.................................................................
+..bssssssssssssssssssssssssssb..................................................................
+................................................................................................
+"
+		);
+	}
+}
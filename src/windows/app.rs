@@ -1,55 +1,117 @@
 use std::sync::{Arc, RwLock};
+use std::time::Instant;
 
 use winit::application::ApplicationHandler;
 use winit::event::{StartCause, WindowEvent};
-use winit::event_loop::{ActiveEventLoop, EventLoopProxy};
+use winit::event_loop::{ActiveEventLoop, ControlFlow, EventLoopProxy};
 use winit::window::WindowId;
 
-use crate::config::Config;
+use crate::config::{Config, Renderer};
+use crate::notifications::{Action, Notification};
 use crate::windows::context_menu::AppContextMenu;
-use crate::windows::egui_glue::EguiWindow;
+use crate::windows::egui_glue::{Backend, EguiWindow};
+use crate::windows::taskbar::{Taskbar, TaskbarLayout};
 use crate::windows::utils::{HwndWithDrop, MultiMap};
+use crate::windows::windows::{DebugOverlay, Toasts};
 
 #[derive(Debug, Clone)]
 pub enum AppMessage {
 	UpdateKomorebiState(crate::komorebi::State),
+	KomorebiConnectionChanged(bool),
 	MenuEvent(muda::MenuEvent),
 	SystemSettingsChanged,
 	DpiChanged,
 	CreateSettingsWindow,
+	/// Starts editing the position of the switcher on the monitor with the
+	/// given handle.
+	EditPosition {
+		monitor: isize,
+	},
 	PreviewConfig(Config),
 	ClearPreviewConfig,
 	CloseWindow(WindowId),
 	RecreateSwitcherWindows,
 	TaskbarRecreated,
+	/// The layout of the taskbar with the given handle changed.
+	TaskbarLayoutChanged {
+		taskbar: isize,
+		layout: TaskbarLayout,
+	},
+	GpuDeviceLost,
+	ToggleDebugOverlay,
+	SaveDiagnostics,
+	OpenLogsFolder,
+	StartKomorebi,
+	StopKomorebi,
+	RestartKomorebi,
+	ReloadKomorebiConfiguration,
+	Notify(Notification),
 }
 
 pub struct App {
-	pub wgpu_instance: wgpu::Instance,
+	pub backend: Backend,
 	pub proxy: EventLoopProxy<AppMessage>,
 	pub windows: MultiMap<WindowId, Option<String>, EguiWindow>,
 	#[allow(unused)]
 	pub tray_icon: Option<crate::windows::tray_icon::TrayIcon>,
 	pub komorebi_state: crate::komorebi::State,
+	/// Whether komorebi is running, otherwise the switchers show the last
+	/// known state greyed out.
+	pub komorebi_connected: bool,
 	#[allow(unused)]
 	pub message_window: HwndWithDrop,
 	pub config: Arc<RwLock<crate::config::Config>>,
 	pub context_menu: AppContextMenu,
+	pub debug_overlay: DebugOverlay,
+	pub debug_overlay_window: Option<WindowId>,
+	pub toasts: Toasts,
+	pub toasts_window: Option<WindowId>,
 }
 
 impl App {
 	pub fn new(proxy: EventLoopProxy<AppMessage>) -> anyhow::Result<Self> {
-		let wgpu_instance = egui_wgpu::wgpu::Instance::new(&wgpu::InstanceDescriptor {
-			backends: wgpu::Backends::DX12,
-			..Default::default()
-		});
+		// Show notifications as toasts
+		{
+			let proxy = proxy.clone();
+			crate::notifications::set_handler(move |notification| {
+				if let Err(e) = proxy.send_event(AppMessage::Notify(notification)) {
+					tracing::error!("Failed to send notification: {e}");
+				}
+			});
+		}
 
-		let komorebi_state = crate::komorebi::read_state().unwrap_or_default();
+		// Offer to open the report of the last crash, if any
+		if let Some(report) = crate::crash::take_pending_report() {
+			tracing::warn!("Found crash report of the last run: {}", report.display());
+			if let Some(reports_dir) = report.parent() {
+				crate::notifications::error_with_action(
+					"komorebi-switcher crashed last time, a crash report was saved",
+					Action::OpenFolder(reports_dir.to_path_buf()),
+				);
+			}
+		}
+
+		let (komorebi_state, komorebi_connected) = match crate::komorebi::read_state() {
+			Ok(state) => (state, true),
+			Err(e) => {
+				tracing::warn!("Failed to read komorebi state, using the last known state: {e}");
+				let state = crate::komorebi::read_cached_state().unwrap_or_default();
+				(state, false)
+			}
+		};
 
 		let message_window = unsafe { crate::windows::message_window::create(proxy.clone())? };
 		let message_window = HwndWithDrop(message_window);
 
-		let config = crate::config::Config::load()?;
+		let config = crate::config::Config::load().unwrap_or_else(|e| {
+			tracing::error!("Failed to load config, using default config: {e}");
+			crate::notifications::error(format!(
+				"Failed to load config, using default config until it is fixed: {e}"
+			));
+			Config::default()
+		});
+
+		let backend = Backend::new(config.renderer, proxy.clone())?;
 		let config = Arc::new(RwLock::new(config));
 
 		let context_menu = AppContextMenu::new(proxy.clone())?;
@@ -60,23 +122,38 @@ impl App {
 		{
 			let proxy = proxy.clone();
 			std::thread::spawn(move || {
-				crate::komorebi::listen_for_state(move |new_state| {
-					if let Err(e) = proxy.send_event(AppMessage::UpdateKomorebiState(new_state)) {
-						tracing::error!("Failed to send komorebi state update: {e}");
-					}
-				})
+				let state_proxy = proxy.clone();
+				crate::komorebi::listen_for_state(
+					move |new_state| {
+						let message = AppMessage::UpdateKomorebiState(new_state);
+						if let Err(e) = state_proxy.send_event(message) {
+							tracing::error!("Failed to send komorebi state update: {e}");
+						}
+					},
+					move |connected| {
+						let message = AppMessage::KomorebiConnectionChanged(connected);
+						if let Err(e) = proxy.send_event(message) {
+							tracing::error!("Failed to send komorebi connection change: {e}");
+						}
+					},
+				)
 			});
 		}
 
 		Ok(Self {
-			wgpu_instance,
+			backend,
 			windows: Default::default(),
 			proxy,
 			tray_icon,
 			komorebi_state,
+			komorebi_connected,
 			message_window,
 			config,
 			context_menu,
+			debug_overlay: DebugOverlay::default(),
+			debug_overlay_window: None,
+			toasts: Toasts::default(),
+			toasts_window: None,
 		})
 	}
 
@@ -85,21 +162,43 @@ impl App {
 
 		tracing::debug!("Found {} taskbars: {taskbars:?}", taskbars.len());
 
-		for monitor in self.komorebi_state.monitors.clone().into_iter() {
+		// Without a known state, show placeholders on the taskbars until
+		// komorebi is running
+		let monitors = if self.komorebi_state.monitors.is_empty() && !self.komorebi_connected {
+			placeholder_monitors(&taskbars)
+		} else {
+			self.komorebi_state.monitors.clone()
+		};
+
+		for monitor in monitors.into_iter() {
 			// skip already existing window for this monitor
 			let monitor_id = monitor.id.clone();
 			if self.windows.contains_key_alt(&Some(monitor_id.clone())) {
 				continue;
 			}
 
-			let Some(taskbar) = taskbars.iter().find(|tb| monitor.rect.contains(tb.rect)) else {
-				tracing::warn!(
-					"Failed to find taskbar for monitor: {}-{} {:?}",
-					monitor.name,
-					monitor.id,
-					monitor.rect
-				);
-				continue;
+			let floating = {
+				let config = self.config.read().unwrap();
+				config.get_monitor(&monitor_id).floating
+			};
+
+			// Match by the taskbar's monitor, as auto-hidden taskbars are mostly
+			// off screen, falling back to the taskbar rect.
+			let taskbar = taskbars.iter().find(|tb| {
+				monitor.rect.contains(tb.monitor_rect) || monitor.rect.contains(tb.rect)
+			});
+			let taskbar = match taskbar {
+				_ if floating => None,
+				Some(taskbar) => Some(*taskbar),
+				None => {
+					tracing::warn!(
+						"Failed to find taskbar for monitor: {}-{} {:?}, using a floating switcher",
+						monitor.name,
+						monitor.id,
+						monitor.rect
+					);
+					None
+				}
 			};
 
 			tracing::info!(
@@ -107,12 +206,12 @@ impl App {
 				monitor.name,
 				monitor.id,
 				monitor.rect,
-				taskbar.hwnd
+				taskbar.map(|tb| tb.hwnd)
 			);
 
 			let window = self.create_switcher_window(
 				event_loop,
-				*taskbar,
+				taskbar,
 				monitor,
 				self.context_menu.clone(),
 			)?;
@@ -123,6 +222,74 @@ impl App {
 		Ok(())
 	}
 
+	/// Replaces the config with the one on disk, keeping the current one if
+	/// it fails to load.
+	fn reload_config(&self) {
+		let config = match Config::load() {
+			Ok(config) => config,
+			Err(e) => {
+				tracing::error!("Failed to reload config: {e}");
+				crate::notifications::error(format!("Failed to reload config: {e}"));
+				return;
+			}
+		};
+
+		let mut current = self.config.write().unwrap();
+		if config.logging.level != current.logging.level {
+			crate::logging::set_level(&config.logging.level);
+		}
+		*current = config;
+	}
+
+	/// Switches to the CPU backend after the GPU couldn't be recreated.
+	///
+	/// Windows created for the GPU have no redirection bitmap, which GDI needs
+	/// to present and can't be added after creation, so they are recreated
+	/// instead of only their surfaces.
+	fn fall_back_to_cpu(&mut self, event_loop: &ActiveEventLoop) -> anyhow::Result<()> {
+		self.backend = Backend::Cpu;
+
+		let debug_overlay = self
+			.debug_overlay_window
+			.is_some_and(|id| self.windows.contains_key(&id));
+
+		self.windows.clear();
+		self.create_switchers(event_loop)?;
+
+		if debug_overlay {
+			self.toggle_debug_overlay(event_loop)?;
+		}
+
+		crate::notifications::warn(
+			"The GPU is unavailable, komorebi-switcher is rendering on the CPU until restarted",
+		);
+
+		Ok(())
+	}
+
+	/// Runs a komorebi action with the configured start command in the
+	/// background, as it waits for komorebi to stop or start, or to answer
+	/// on its socket, which would block the switchers if komorebi hangs.
+	fn spawn_komorebi_action(
+		&self,
+		name: &'static str,
+		action: impl FnOnce(&str) -> anyhow::Result<()> + Send + 'static,
+	) {
+		let command = self
+			.config
+			.read()
+			.unwrap()
+			.komorebi_start_command()
+			.to_string();
+
+		std::thread::spawn(move || {
+			if let Err(e) = action(&command) {
+				tracing::error!("Failed to {name} komorebi: {e}");
+				crate::notifications::error(format!("Failed to {name} komorebi: {e}"));
+			}
+		});
+	}
+
 	fn handle_app_message(
 		&mut self,
 		event_loop: &ActiveEventLoop,
@@ -131,6 +298,33 @@ impl App {
 		match message {
 			AppMessage::CreateSettingsWindow => self.create_settings_window(event_loop)?,
 
+			AppMessage::ToggleDebugOverlay => self.toggle_debug_overlay(event_loop)?,
+
+			AppMessage::SaveDiagnostics => self.save_diagnostics()?,
+
+			AppMessage::OpenLogsFolder => self.open_logs_folder()?,
+
+			AppMessage::StartKomorebi => {
+				self.spawn_komorebi_action("start", crate::komorebi::start)
+			}
+
+			AppMessage::StopKomorebi => {
+				self.spawn_komorebi_action("stop", |_| crate::komorebi::stop())
+			}
+
+			AppMessage::RestartKomorebi => {
+				self.spawn_komorebi_action("restart", crate::komorebi::restart)
+			}
+
+			AppMessage::ReloadKomorebiConfiguration => self
+				.spawn_komorebi_action("reload the configuration of", |_| {
+					crate::komorebi::reload_configuration()
+				}),
+
+			AppMessage::Notify(notification) => {
+				self.show_notification(event_loop, notification.clone())?
+			}
+
 			AppMessage::CloseWindow(window_id) => {
 				self.windows.remove(window_id);
 			}
@@ -151,17 +345,57 @@ impl App {
 					let monitor = state.monitors.iter().any(|m| &m.id == key);
 					if !monitor {
 						tracing::info!("Removing switcher window for {key}");
+						self.debug_overlay.remove(key);
 					}
 
 					monitor
 				});
 			}
 
+			AppMessage::KomorebiConnectionChanged(connected) => {
+				tracing::info!("komorebi connected: {connected}");
+				self.komorebi_connected = *connected;
+
+				// Keep the last known state for the next launch
+				if !connected {
+					if let Err(e) = crate::komorebi::save_cached_state(&self.komorebi_state) {
+						tracing::error!("Failed to cache komorebi state: {e}");
+					}
+				}
+			}
+
+			AppMessage::GpuDeviceLost => {
+				tracing::warn!("Recreating GPU context and surfaces of all windows");
+
+				let renderer = self.config.read().unwrap().renderer;
+				match Backend::new_gpu(self.proxy.clone()) {
+					Ok(backend) => {
+						self.backend = backend;
+
+						for window in self.windows.values_mut() {
+							window.recreate_surface(&self.backend)?;
+						}
+					}
+					Err(e) if renderer == Renderer::Auto => {
+						tracing::error!("Failed to recreate GPU context, falling back to CPU: {e}");
+						self.fall_back_to_cpu(event_loop)?;
+					}
+					Err(e) => return Err(e),
+				}
+			}
+
 			AppMessage::RecreateSwitcherWindows | AppMessage::TaskbarRecreated => {
 				tracing::info!("Received {message:?}, closing and recreating all switchers");
 
-				// Close all existing switcher windows
-				self.windows.clear();
+				// Reload the config and user themes from disk, i.e after editing them by hand
+				if matches!(message, AppMessage::RecreateSwitcherWindows) {
+					self.reload_config();
+				}
+				crate::theme::clear_cache();
+
+				// Close all existing switcher windows, keeping the settings
+				// window and debug overlay
+				self.windows.retain(|_, key, _| key.is_none());
 
 				// Recreate switchers with new taskbar windows
 				self.create_switchers(event_loop)?;
@@ -181,28 +415,64 @@ impl ApplicationHandler<AppMessage> for App {
 			if let Err(e) = self.create_switchers(event_loop) {
 				tracing::error!("Error while creating switchers: {e}");
 			};
+
+			let debug_overlay = self.config.read().unwrap().debug_overlay;
+			if debug_overlay {
+				if let Err(e) = self.toggle_debug_overlay(event_loop) {
+					tracing::error!("Error while creating debug overlay: {e}");
+				}
+			}
 		}
 	}
 
 	fn resumed(&mut self, _event_loop: &ActiveEventLoop) {}
 
+	fn exiting(&mut self, _event_loop: &ActiveEventLoop) {
+		if self.komorebi_connected {
+			if let Err(e) = crate::komorebi::save_cached_state(&self.komorebi_state) {
+				tracing::error!("Failed to cache komorebi state: {e}");
+			}
+		}
+	}
+
 	fn user_event(&mut self, event_loop: &ActiveEventLoop, event: AppMessage) {
 		if let Err(e) = self.handle_app_message(event_loop, &event) {
-			tracing::error!("Error while handling AppMessage: {e}")
+			tracing::error!("Error while handling AppMessage: {e}");
+			crate::notifications::error(e.to_string());
 		}
 
 		if let Err(e) = self.context_menu.handle_app_message(event_loop, &event) {
-			tracing::error!("Error while handling AppMessage for context menu: {e}")
+			tracing::error!("Error while handling AppMessage for context menu: {e}");
+			crate::notifications::error(e.to_string());
 		}
 
 		for window in self.windows.values_mut() {
-			let ctx = window.surface.egui_renderer.egui_ctx();
+			let ctx = window.surface.egui_ctx();
 			if let Err(e) = window.view.handle_app_message(ctx, event_loop, &event) {
-				tracing::error!("Error while handling AppMessage for window: {e}")
+				tracing::error!("Error while handling AppMessage for window: {e}");
+				crate::notifications::error(e.to_string());
 			}
+		}
+	}
+
+	fn about_to_wait(&mut self, event_loop: &ActiveEventLoop) {
+		// Redraw the windows whose repaint is due, and wake up for the next one
+		let now = Instant::now();
+		let mut next_frame: Option<Instant> = None;
 
-			window.request_redraw();
+		for window in self.windows.values_mut() {
+			match window.repaint.next_frame() {
+				Some(at) if at <= now => window.request_redraw(),
+				Some(at) => next_frame = Some(next_frame.map_or(at, |next| next.min(at))),
+				None => {}
+			}
 		}
+
+		let control_flow = match next_frame {
+			Some(at) => ControlFlow::WaitUntil(at),
+			None => ControlFlow::Wait,
+		};
+		event_loop.set_control_flow(control_flow);
 	}
 
 	fn window_event(
@@ -236,3 +506,19 @@ impl ApplicationHandler<AppMessage> for App {
 		}
 	}
 }
+
+/// Creates a placeholder monitor for each taskbar, used when komorebi isn't
+/// running and no state was cached yet.
+fn placeholder_monitors(taskbars: &[Taskbar]) -> Vec<crate::komorebi::Monitor> {
+	taskbars
+		.iter()
+		.enumerate()
+		.map(|(index, taskbar)| crate::komorebi::Monitor {
+			name: "placeholder".to_string(),
+			index,
+			id: format!("placeholder-{index}"),
+			rect: taskbar.monitor_rect.into(),
+			..Default::default()
+		})
+		.collect()
+}
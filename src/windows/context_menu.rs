@@ -1,4 +1,4 @@
-use muda::{Menu, PredefinedMenuItem};
+use muda::{Menu, PredefinedMenuItem, Submenu};
 use tray_icon::menu::MenuItem;
 use winit::event_loop::{ActiveEventLoop, EventLoopProxy};
 
@@ -9,16 +9,44 @@ pub struct AppContextMenu {
 	pub proxy: EventLoopProxy<AppMessage>,
 	pub menu: Menu,
 	pub settings: MenuItem,
+	pub edit_position: MenuItem,
 	pub refresh: MenuItem,
+	/// Only shown in debug builds, see the `debug_overlay` setting otherwise.
+	pub debug_overlay: MenuItem,
+	pub save_diagnostics: MenuItem,
+	pub open_logs_folder: MenuItem,
+	pub start_komorebi: MenuItem,
+	pub stop_komorebi: MenuItem,
+	pub restart_komorebi: MenuItem,
+	pub reload_komorebi: MenuItem,
 	pub quit: MenuItem,
 }
 
 impl AppContextMenu {
 	pub fn new(proxy: EventLoopProxy<AppMessage>) -> anyhow::Result<Self> {
 		let settings = MenuItem::new("Settings", true, None);
+		let edit_position = MenuItem::new("Edit position", true, None);
 		let refresh = MenuItem::new("Refresh", true, None);
+		let debug_overlay = MenuItem::new("Debug overlay", true, None);
+		let save_diagnostics = MenuItem::new("Save diagnostics...", true, None);
+		let open_logs_folder = MenuItem::new("Open logs folder", true, None);
 		let separator = PredefinedMenuItem::separator();
 
+		let start_komorebi = MenuItem::new("Start", true, None);
+		let stop_komorebi = MenuItem::new("Stop", true, None);
+		let restart_komorebi = MenuItem::new("Restart", true, None);
+		let reload_komorebi = MenuItem::new("Reload configuration", true, None);
+		let komorebi = Submenu::with_items(
+			"komorebi",
+			true,
+			&[
+				&start_komorebi,
+				&stop_komorebi,
+				&restart_komorebi,
+				&reload_komorebi,
+			],
+		)?;
+
 		#[cfg(debug_assertions)]
 		let title = concat!(env!("CARGO_PKG_NAME"), " (debug)");
 		#[cfg(not(debug_assertions))]
@@ -29,14 +57,36 @@ impl AppContextMenu {
 		let quit = MenuItem::new("Quit", true, None);
 
 		let menu = Menu::with_items(&[
-			&settings, &refresh, &separator, &title, &version, &separator, &quit,
+			&settings,
+			&edit_position,
+			&refresh,
+			&save_diagnostics,
+			&open_logs_folder,
+			&separator,
+			&komorebi,
+			&separator,
+			&title,
+			&version,
+			&separator,
+			&quit,
 		])?;
 
+		#[cfg(debug_assertions)]
+		menu.insert(&debug_overlay, 3)?;
+
 		Ok(Self {
 			proxy,
 			menu,
 			settings,
+			edit_position,
 			refresh,
+			debug_overlay,
+			save_diagnostics,
+			open_logs_folder,
+			start_komorebi,
+			stop_komorebi,
+			restart_komorebi,
+			reload_komorebi,
 			quit,
 		})
 	}
@@ -50,9 +100,37 @@ impl AppContextMenu {
 			AppMessage::MenuEvent(event) if *event.id() == self.settings.id() => {
 				self.proxy.send_event(AppMessage::CreateSettingsWindow)?
 			}
+			AppMessage::MenuEvent(event) if *event.id() == self.edit_position.id() => {
+				// The menu opens on the monitor it was invoked from, from a switcher
+				// or the tray icon, so the cursor is still on it when clicking
+				let monitor = crate::windows::utils::cursor_monitor();
+				self.proxy
+					.send_event(AppMessage::EditPosition { monitor })?
+			}
 			AppMessage::MenuEvent(event) if *event.id() == self.refresh.id() => {
 				self.proxy.send_event(AppMessage::RecreateSwitcherWindows)?
 			}
+			AppMessage::MenuEvent(event) if *event.id() == self.debug_overlay.id() => {
+				self.proxy.send_event(AppMessage::ToggleDebugOverlay)?
+			}
+			AppMessage::MenuEvent(event) if *event.id() == self.save_diagnostics.id() => {
+				self.proxy.send_event(AppMessage::SaveDiagnostics)?
+			}
+			AppMessage::MenuEvent(event) if *event.id() == self.open_logs_folder.id() => {
+				self.proxy.send_event(AppMessage::OpenLogsFolder)?
+			}
+			AppMessage::MenuEvent(event) if *event.id() == self.start_komorebi.id() => {
+				self.proxy.send_event(AppMessage::StartKomorebi)?
+			}
+			AppMessage::MenuEvent(event) if *event.id() == self.stop_komorebi.id() => {
+				self.proxy.send_event(AppMessage::StopKomorebi)?
+			}
+			AppMessage::MenuEvent(event) if *event.id() == self.restart_komorebi.id() => {
+				self.proxy.send_event(AppMessage::RestartKomorebi)?
+			}
+			AppMessage::MenuEvent(event) if *event.id() == self.reload_komorebi.id() => self
+				.proxy
+				.send_event(AppMessage::ReloadKomorebiConfiguration)?,
 			AppMessage::MenuEvent(event) if *event.id() == self.quit.id() => event_loop.exit(),
 			_ => {}
 		}
@@ -0,0 +1,234 @@
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::fs::File;
+use std::io::{BufWriter, Write as _};
+use std::path::Path;
+
+use windows::Win32::UI::HiDpi::GetDpiForSystem;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+use crate::config::Config;
+use crate::redact::Redactor;
+use crate::windows::app::App;
+use crate::windows::egui_glue::Backend;
+use crate::windows::taskbar::{self, TaskbarLayout};
+
+const DEFAULT_FILE_NAME: &str = "komorebi-switcher-diagnostics.zip";
+
+/// Number of most recent log files included in the bundle.
+const MAX_LOG_FILES: usize = 3;
+
+impl App {
+	/// Asks where to save a diagnostics bundle, and writes it there.
+	///
+	/// The dialog blocks until closed, so it is shown on another thread
+	/// with the app's own details gathered beforehand.
+	pub fn save_diagnostics(&self) -> anyhow::Result<()> {
+		let details = AppDetails {
+			info: self.info(),
+			resolved_config: self.resolved_config()?,
+			monitors: self.monitors(),
+		};
+
+		std::thread::spawn(move || {
+			let Some(path) = rfd::FileDialog::new()
+				.set_title("Save diagnostics")
+				.set_file_name(DEFAULT_FILE_NAME)
+				.add_filter("Zip archive", &["zip"])
+				.save_file()
+			else {
+				return;
+			};
+
+			tracing::info!("Saving diagnostics to {}", path.display());
+
+			if let Err(e) = write_diagnostics(&path, &details) {
+				tracing::error!("Failed to save diagnostics: {e}");
+				crate::notifications::error(format!("Failed to save diagnostics: {e}"));
+			}
+		});
+
+		Ok(())
+	}
+
+	/// Opens the directory of the log files in the file explorer.
+	pub fn open_logs_folder(&self) -> anyhow::Result<()> {
+		let logs_dir = match crate::logging::logs_dir() {
+			Some(logs_dir) => logs_dir.to_path_buf(),
+			None => self.config.read().unwrap().logging.directory()?,
+		};
+
+		std::fs::create_dir_all(&logs_dir)?;
+		std::process::Command::new("explorer")
+			.arg(&logs_dir)
+			.spawn()?;
+
+		Ok(())
+	}
+
+	fn info(&self) -> String {
+		let backend = match self.backend {
+			Backend::Gpu(_) => "GPU",
+			Backend::Cpu => "CPU",
+		};
+
+		let mut info = String::new();
+		let _ = writeln!(
+			info,
+			"{} v{}",
+			env!("CARGO_PKG_NAME"),
+			env!("CARGO_PKG_VERSION")
+		);
+		let _ = writeln!(info, "Debug build: {}", cfg!(debug_assertions));
+		let _ = writeln!(info, "Renderer: {backend}");
+		let _ = writeln!(
+			info,
+			"Komorebi connected: {}",
+			crate::komorebi::listener_stats().connected
+		);
+		info
+	}
+
+	/// The config of each komorebi monitor, with the global config merged in.
+	fn resolved_config(&self) -> anyhow::Result<String> {
+		let config = self.config.read().unwrap();
+		let dark_mode = crate::windows::utils::is_system_dark_mode();
+		let komorebi_theme = self.komorebi_state.theme.as_ref();
+
+		let monitors = self
+			.komorebi_state
+			.monitors
+			.iter()
+			.map(|m| {
+				let monitor =
+					config.resolve_monitor(&m.id, &m.workspaces, dark_mode, komorebi_theme);
+				(m.id.clone(), monitor)
+			})
+			.collect::<BTreeMap<_, _>>();
+
+		Ok(toml::to_string_pretty(&monitors)?)
+	}
+
+	fn monitors(&self) -> String {
+		let mut monitors = String::new();
+
+		let _ = writeln!(monitors, "System DPI: {}", unsafe { GetDpiForSystem() });
+
+		for monitor in &self.komorebi_state.monitors {
+			let _ = writeln!(
+				monitors,
+				"{}: {} {:?} (index {}, focused: {})",
+				monitor.id, monitor.name, monitor.rect, monitor.index, monitor.focused
+			);
+		}
+
+		monitors
+	}
+}
+
+/// Details of the app, gathered on the event loop.
+struct AppDetails {
+	info: String,
+	resolved_config: String,
+	monitors: String,
+}
+
+/// Writes a zip with the logs, config, komorebi state, taskbars and monitors,
+/// with the home directory and user name redacted.
+fn write_diagnostics(path: &Path, details: &AppDetails) -> anyhow::Result<()> {
+	let user = std::env::var("USERNAME").ok();
+	let redactor = Redactor::new(
+		dirs::home_dir().as_deref(),
+		crate::logging::logs_dir(),
+		user.as_deref(),
+	);
+
+	let file = BufWriter::new(File::create(path)?);
+	let mut zip = ZipWriter::new(file);
+
+	let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+
+	let mut add_file = |name: &str, content: &str| -> anyhow::Result<()> {
+		zip.start_file(name, options)?;
+		zip.write_all(redactor.redact(content).as_bytes())?;
+		Ok(())
+	};
+
+	add_file("info.txt", &details.info)?;
+	add_file("config.toml", &read_config())?;
+	add_file("config.resolved.toml", &details.resolved_config)?;
+	add_file("komorebi-state.json", &read_komorebi_state())?;
+	add_file("taskbars.txt", &taskbars())?;
+	add_file("monitors.txt", &details.monitors)?;
+
+	for (name, content) in recent_logs() {
+		add_file(&format!("logs/{name}"), &content)?;
+	}
+
+	zip.finish()?.flush()?;
+
+	Ok(())
+}
+
+fn read_config() -> String {
+	let config = Config::path().and_then(|path| Ok(std::fs::read_to_string(path)?));
+	config.unwrap_or_else(|e| format!("Failed to read config: {e}"))
+}
+
+fn read_komorebi_state() -> String {
+	crate::komorebi::read_raw_state()
+		.unwrap_or_else(|e| format!("Failed to read komorebi state: {e}"))
+}
+
+/// The detected taskbars and their layout, including their DPI.
+fn taskbars() -> String {
+	let mut taskbars = String::new();
+
+	for taskbar in taskbar::all() {
+		let _ = writeln!(taskbars, "{taskbar:?}");
+		match TaskbarLayout::new(taskbar.hwnd) {
+			Ok(layout) => {
+				let _ = writeln!(taskbars, "{layout:?}");
+			}
+			Err(e) => {
+				let _ = writeln!(taskbars, "Failed to get layout: {e}");
+			}
+		}
+		let _ = writeln!(taskbars);
+	}
+
+	taskbars
+}
+
+/// Reads our most recently modified log files.
+fn recent_logs() -> Vec<(String, String)> {
+	let Some(logs_dir) = crate::logging::logs_dir() else {
+		return Vec::new();
+	};
+
+	let mut logs = std::fs::read_dir(logs_dir)
+		.into_iter()
+		.flatten()
+		.filter_map(|entry| {
+			let entry = entry.ok()?;
+			if !crate::logging::is_log_file(&entry.file_name().to_string_lossy()) {
+				return None;
+			}
+
+			let modified = entry.metadata().and_then(|m| m.modified()).ok()?;
+			Some((entry, modified))
+		})
+		.collect::<Vec<_>>();
+
+	logs.sort_by_key(|(_, modified)| std::cmp::Reverse(*modified));
+
+	logs.into_iter()
+		.take(MAX_LOG_FILES)
+		.filter_map(|(entry, _)| {
+			let content = std::fs::read(entry.path()).ok()?;
+			let name = entry.file_name().to_string_lossy().into_owned();
+			Some((name, String::from_utf8_lossy(&content).into_owned()))
+		})
+		.collect()
+}
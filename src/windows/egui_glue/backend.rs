@@ -0,0 +1,51 @@
+use std::sync::Arc;
+
+use winit::event_loop::EventLoopProxy;
+use winit::window::Window;
+
+use super::software_surface::SoftwareSurface;
+use super::surface::Surface;
+use super::wgpu_surface::WgpuSurface;
+use super::GpuContext;
+use crate::config::Renderer;
+use crate::windows::app::AppMessage;
+
+/// Backend used to render all windows.
+#[derive(Clone)]
+pub enum Backend {
+	/// wgpu on DirectX 12, presented through DirectComposition.
+	Gpu(Arc<GpuContext>),
+	/// A CPU rasterizer, presented through GDI.
+	Cpu,
+}
+
+impl Backend {
+	pub fn new(renderer: Renderer, proxy: EventLoopProxy<AppMessage>) -> anyhow::Result<Self> {
+		match renderer {
+			Renderer::Auto => Self::new_gpu(proxy).or_else(|e| {
+				tracing::warn!("Failed to initialize GPU renderer, falling back to CPU: {e}");
+				Ok(Self::Cpu)
+			}),
+			Renderer::Gpu => Self::new_gpu(proxy),
+			Renderer::Cpu => Ok(Self::Cpu),
+		}
+	}
+
+	pub fn new_gpu(proxy: EventLoopProxy<AppMessage>) -> anyhow::Result<Self> {
+		let gpu = pollster::block_on(GpuContext::new(proxy))?;
+		Ok(Self::Gpu(Arc::new(gpu)))
+	}
+
+	/// Whether windows should be created without a redirection bitmap,
+	/// which the GPU backend doesn't need but GDI requires to present.
+	pub fn no_redirection_bitmap(&self) -> bool {
+		matches!(self, Self::Gpu(_))
+	}
+
+	pub fn create_surface(&self, window: &Arc<Window>) -> anyhow::Result<Box<dyn Surface>> {
+		match self {
+			Self::Gpu(gpu) => Ok(Box::new(WgpuSurface::new(window, gpu)?)),
+			Self::Cpu => Ok(Box::new(SoftwareSurface::new(window)?)),
+		}
+	}
+}
@@ -2,6 +2,8 @@ use std::sync::Arc;
 
 use winit::window::Window;
 
+use super::surface::create_egui_state;
+
 pub struct EguiRenderer {
 	pub state: egui_winit::State,
 	renderer: egui_wgpu::Renderer,
@@ -16,23 +18,7 @@ impl EguiRenderer {
 		msaa_samples: u32,
 		window: &Arc<Window>,
 	) -> Self {
-		let egui_context = egui::Context::default();
-
-		{
-			let window = window.clone();
-			egui_context.set_request_repaint_callback(move |_| {
-				window.request_redraw();
-			});
-		}
-
-		let egui_state = egui_winit::State::new(
-			egui_context,
-			egui::viewport::ViewportId::ROOT,
-			&window,
-			Some(window.scale_factor() as f32),
-			window.theme(),
-			Some(2 * 1024), // default dimension is 2048
-		);
+		let egui_state = create_egui_state(window);
 
 		let egui_renderer = egui_wgpu::Renderer::new(
 			device,
@@ -26,5 +26,9 @@ pub trait EguiView: std::any::Any {
 		Ok(())
 	}
 
+	/// Called when the egui context was recreated, to reset any state that
+	/// was applied to the old one.
+	fn handle_egui_context_recreated(&mut self, _ctx: &egui::Context) {}
+
 	fn update(&mut self, ctx: &egui::Context);
 }
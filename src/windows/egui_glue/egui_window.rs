@@ -7,12 +7,14 @@ use winit::event_loop::ActiveEventLoop;
 use winit::window::Window;
 
 use super::egui_view::EguiView;
-use super::wgpu_surface::WgpuSurface;
+use super::surface::Surface;
+use super::{Backend, RepaintScheduler};
 
 pub struct EguiWindow {
 	pub window: Arc<Window>,
-	pub surface: WgpuSurface,
+	pub surface: Box<dyn Surface>,
 	pub view: Box<dyn EguiView>,
+	pub repaint: RepaintScheduler,
 }
 
 impl Deref for EguiWindow {
@@ -26,17 +28,36 @@ impl Deref for EguiWindow {
 impl EguiWindow {
 	pub fn new(
 		window: Arc<Window>,
-		instance: &wgpu::Instance,
+		backend: &Backend,
 		view: impl EguiView + 'static,
 	) -> anyhow::Result<Self> {
-		let surface = pollster::block_on(WgpuSurface::new(&window, instance))?;
+		let surface = backend.create_surface(&window)?;
+
+		let repaint = RepaintScheduler::default();
+		repaint.install(surface.egui_ctx());
+		surface.egui_ctx().request_repaint();
+
 		Ok(Self {
 			window,
 			surface,
 			view: Box::new(view),
+			repaint,
 		})
 	}
 
+	/// Recreates the surface with a new backend, i.e after the GPU device was lost.
+	pub fn recreate_surface(&mut self, backend: &Backend) -> anyhow::Result<()> {
+		self.surface.recreate(&self.window, backend)?;
+
+		let ctx = self.surface.egui_ctx();
+		self.repaint.install(ctx);
+		self.view.handle_egui_context_recreated(ctx);
+
+		ctx.request_repaint();
+
+		Ok(())
+	}
+
 	pub fn handle_input(&mut self, event: &WindowEvent) -> egui_winit::EventResponse {
 		self.surface.handle_input(&self.window, event)
 	}
@@ -52,19 +73,24 @@ impl EguiWindow {
 	) -> anyhow::Result<()> {
 		let resposne = self.handle_input(&event);
 
-		if resposne.repaint {
+		if event == WindowEvent::RedrawRequested {
 			self.handle_redraw()?;
+		} else if resposne.repaint {
+			self.surface.egui_ctx().request_repaint();
 		}
 
 		if let WindowEvent::Resized(size) = event {
 			self.handle_resized(size);
 		}
 
-		let ctx = self.surface.egui_renderer.egui_ctx();
+		let ctx = self.surface.egui_ctx();
 		self.view.handle_window_event(ctx, event_loop, event)
 	}
 
 	pub fn handle_redraw(&mut self) -> anyhow::Result<()> {
-		self.surface.handle_redraw(&self.window, self.view.as_mut())
+		self.repaint.begin_frame(self.window.id());
+		let result = self.surface.handle_redraw(&self.window, self.view.as_mut());
+		self.repaint.end_frame(self.surface.egui_ctx());
+		result
 	}
 }
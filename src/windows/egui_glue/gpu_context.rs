@@ -0,0 +1,65 @@
+use anyhow::Context;
+use winit::event_loop::EventLoopProxy;
+
+use crate::windows::app::AppMessage;
+
+/// GPU adapter, device and queue shared by the surfaces of all windows.
+pub struct GpuContext {
+	pub instance: wgpu::Instance,
+	pub adapter: wgpu::Adapter,
+	pub device: wgpu::Device,
+	pub queue: wgpu::Queue,
+}
+
+impl GpuContext {
+	/// Creates the GPU context, sending [`AppMessage::GpuDeviceLost`] if the
+	/// device is lost later on, i.e after a driver update or a GPU reset.
+	pub async fn new(proxy: EventLoopProxy<AppMessage>) -> anyhow::Result<Self> {
+		let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor {
+			backends: wgpu::Backends::DX12,
+			..Default::default()
+		});
+
+		let adapter = instance
+			.request_adapter(&wgpu::RequestAdapterOptions {
+				power_preference: wgpu::PowerPreference::LowPower,
+				force_fallback_adapter: false,
+				compatible_surface: None,
+			})
+			.await
+			.context("Failed to find an appropriate adapter")?;
+
+		tracing::info!("Using GPU adapter: {:?}", adapter.get_info());
+
+		let (device, queue) = adapter
+			.request_device(
+				&wgpu::DeviceDescriptor {
+					label: None,
+					required_features: wgpu::Features::empty(),
+					required_limits: wgpu::Limits::default(),
+					memory_hints: wgpu::MemoryHints::MemoryUsage,
+				},
+				None,
+			)
+			.await?;
+
+		device.set_device_lost_callback(move |reason, message| {
+			// Destroyed is expected when the device is dropped after recovery
+			if reason == wgpu::DeviceLostReason::Destroyed {
+				return;
+			}
+
+			tracing::error!("GPU device lost ({reason:?}): {message}");
+			if let Err(e) = proxy.send_event(AppMessage::GpuDeviceLost) {
+				tracing::error!("Failed to send `AppMessage::GpuDeviceLost`: {e}")
+			}
+		});
+
+		Ok(Self {
+			instance,
+			adapter,
+			device,
+			queue,
+		})
+	}
+}
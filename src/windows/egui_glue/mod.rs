@@ -1,8 +1,17 @@
+mod backend;
 mod dx12_surface;
 mod egui_renderer;
 mod egui_view;
 mod egui_window;
+mod gpu_context;
+mod repaint;
+mod software_surface;
+mod surface;
 mod wgpu_surface;
 
+pub use backend::*;
 pub use egui_view::*;
 pub use egui_window::*;
+pub use gpu_context::*;
+pub use repaint::*;
+pub use surface::Surface;
@@ -0,0 +1,106 @@
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use winit::window::WindowId;
+
+/// Minimum time between two frames of a window, capping animations at 60 frames per second.
+const MIN_FRAME_INTERVAL: Duration = Duration::from_micros(16_667);
+
+/// Interval at which the frame rate is measured.
+const FPS_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Schedules the repaints requested by egui, so that a window is only
+/// redrawn when something changed, and at most every [`MIN_FRAME_INTERVAL`].
+#[derive(Debug, Default)]
+pub struct RepaintScheduler {
+	/// Earliest repaint requested by egui since the last frame,
+	/// shared with the repaint callback of the egui context.
+	requested: Arc<Mutex<Option<Instant>>>,
+	last_frame: Option<Instant>,
+	stats: FrameStats,
+	/// Frames rendered since `fps_start`.
+	frames: u32,
+	fps_start: Option<Instant>,
+}
+
+/// Duration and rate of the frames of a window, see [`FrameStats::get`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FrameStats {
+	/// Time it took to render the last frame.
+	pub frame_time: Duration,
+	/// Frames rendered per second, measured over at least [`FPS_INTERVAL`].
+	pub fps: u32,
+}
+
+impl FrameStats {
+	/// Gets the stats of the frames rendered before the current one
+	/// with the given egui context.
+	pub fn get(ctx: &egui::Context) -> Self {
+		ctx.data(|d| d.get_temp(Self::id())).unwrap_or_default()
+	}
+
+	fn id() -> egui::Id {
+		egui::Id::new("frame_stats")
+	}
+}
+
+impl RepaintScheduler {
+	/// Routes the repaint requests of the egui context to the scheduler.
+	pub fn install(&self, ctx: &egui::Context) {
+		let requested = self.requested.clone();
+		ctx.set_request_repaint_callback(move |info| {
+			// Far away delays mean no repaint
+			let Some(at) = Instant::now().checked_add(info.delay) else {
+				return;
+			};
+
+			let mut requested = requested.lock().unwrap();
+			*requested = Some(requested.map_or(at, |requested| requested.min(at)));
+		});
+	}
+
+	/// Gets when the window should be redrawn next, if a repaint was requested.
+	pub fn next_frame(&self) -> Option<Instant> {
+		let requested = (*self.requested.lock().unwrap())?;
+
+		let capped = match self.last_frame {
+			Some(last_frame) => requested.max(last_frame + MIN_FRAME_INTERVAL),
+			None => requested,
+		};
+
+		Some(capped)
+	}
+
+	/// Clears the pending repaint request before rendering a frame, so that
+	/// requests made while rendering it (i.e by animations) schedule the next one.
+	pub fn begin_frame(&mut self, window_id: WindowId) {
+		let now = Instant::now();
+
+		self.requested.lock().unwrap().take();
+		self.last_frame = Some(now);
+
+		let elapsed = now - *self.fps_start.get_or_insert(now);
+		if elapsed >= FPS_INTERVAL {
+			// Idle periods are averaged in, as nothing is rendered while idle
+			self.stats.fps = (self.frames as f32 / elapsed.as_secs_f32()).round() as u32;
+			self.frames = 0;
+			self.fps_start = Some(now);
+
+			let fps = self.stats.fps;
+			tracing::debug!("Window {window_id:?} rendered {fps} frames per second");
+		}
+
+		self.frames += 1;
+	}
+
+	/// Records the time it took to render the frame, and stores the frame
+	/// stats in the egui context.
+	pub fn end_frame(&mut self, ctx: &egui::Context) {
+		if let Some(last_frame) = self.last_frame {
+			self.stats.frame_time = last_frame.elapsed();
+		}
+
+		let stats = self.stats;
+		ctx.data_mut(|d| d.insert_temp(FrameStats::id(), stats));
+	}
+}
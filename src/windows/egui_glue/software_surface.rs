@@ -0,0 +1,211 @@
+use std::sync::Arc;
+
+use raw_window_handle::{HasWindowHandle, RawWindowHandle};
+use tiny_skia::Pixmap;
+use windows::Win32::Foundation::{COLORREF, HWND, POINT, SIZE};
+use windows::Win32::Graphics::Gdi::*;
+use windows::Win32::UI::WindowsAndMessaging::*;
+use winit::event::WindowEvent;
+use winit::window::Window;
+
+use super::surface::{create_egui_state, Surface};
+use super::{Backend, EguiView};
+use crate::rasterizer::Rasterizer;
+
+/// Renders egui on the CPU and presents it through GDI.
+///
+/// Undecorated windows (i.e the switcher) are made layered to keep
+/// per-pixel transparency, others are drawn onto directly.
+pub struct SoftwareSurface {
+	state: egui_winit::State,
+	rasterizer: Rasterizer,
+	pixmap: Pixmap,
+	hwnd: HWND,
+	layered: bool,
+}
+
+impl SoftwareSurface {
+	pub fn new(window: &Arc<Window>) -> anyhow::Result<Self> {
+		let hwnd = window.window_handle()?;
+		let RawWindowHandle::Win32(hwnd) = hwnd.as_raw() else {
+			anyhow::bail!("Window handle must be win32");
+		};
+		let hwnd = HWND(hwnd.hwnd.get() as _);
+
+		let layered = !window.is_decorated();
+		if layered {
+			unsafe {
+				let ex_style = GetWindowLongPtrW(hwnd, GWL_EXSTYLE);
+				SetWindowLongPtrW(hwnd, GWL_EXSTYLE, ex_style | WS_EX_LAYERED.0 as isize);
+			}
+		}
+
+		let size = window.inner_size();
+		let pixmap = new_pixmap(size.width, size.height)?;
+
+		Ok(Self {
+			state: create_egui_state(window),
+			rasterizer: Rasterizer::default(),
+			pixmap,
+			hwnd,
+			layered,
+		})
+	}
+
+	fn present(&self) -> anyhow::Result<()> {
+		let width = self.pixmap.width() as i32;
+		let height = self.pixmap.height() as i32;
+
+		// GDI expects BGRA while tiny-skia stores RGBA, both premultiplied
+		let mut pixels = self.pixmap.data().to_vec();
+		for pixel in pixels.chunks_exact_mut(4) {
+			pixel.swap(0, 2);
+		}
+
+		let info = BITMAPINFO {
+			bmiHeader: BITMAPINFOHEADER {
+				biSize: std::mem::size_of::<BITMAPINFOHEADER>() as u32,
+				biWidth: width,
+				// Negative height for a top-down bitmap
+				biHeight: -height,
+				biPlanes: 1,
+				biBitCount: 32,
+				biCompression: BI_RGB.0,
+				..Default::default()
+			},
+			..Default::default()
+		};
+
+		if !self.layered {
+			unsafe {
+				let hdc = GetDC(Some(self.hwnd));
+				SetDIBitsToDevice(
+					hdc,
+					0,
+					0,
+					width as u32,
+					height as u32,
+					0,
+					0,
+					0,
+					height as u32,
+					pixels.as_ptr() as _,
+					&info,
+					DIB_RGB_COLORS,
+				);
+				ReleaseDC(Some(self.hwnd), hdc);
+			}
+
+			return Ok(());
+		}
+
+		unsafe {
+			let hdc = CreateCompatibleDC(None);
+
+			let mut bits = std::ptr::null_mut();
+			let bitmap =
+				match CreateDIBSection(Some(hdc), &info, DIB_RGB_COLORS, &mut bits, None, 0) {
+					Ok(bitmap) => bitmap,
+					Err(e) => {
+						let _ = DeleteDC(hdc);
+						return Err(e.into());
+					}
+				};
+
+			std::ptr::copy_nonoverlapping(pixels.as_ptr(), bits as *mut u8, pixels.len());
+
+			let previous = SelectObject(hdc, bitmap.into());
+
+			let size = SIZE {
+				cx: width,
+				cy: height,
+			};
+			let blend = BLENDFUNCTION {
+				BlendOp: AC_SRC_OVER as u8,
+				BlendFlags: 0,
+				SourceConstantAlpha: 255,
+				AlphaFormat: AC_SRC_ALPHA as u8,
+			};
+
+			let result = UpdateLayeredWindow(
+				self.hwnd,
+				None,
+				None,
+				Some(&size as *const _),
+				Some(hdc),
+				Some(&POINT::default() as *const _),
+				COLORREF(0),
+				Some(&blend as *const _),
+				ULW_ALPHA,
+			);
+
+			SelectObject(hdc, previous);
+			let _ = DeleteObject(bitmap.into());
+			let _ = DeleteDC(hdc);
+
+			result?;
+		}
+
+		Ok(())
+	}
+}
+
+impl Surface for SoftwareSurface {
+	fn egui_ctx(&self) -> &egui::Context {
+		self.state.egui_ctx()
+	}
+
+	fn handle_input(&mut self, window: &Window, event: &WindowEvent) -> egui_winit::EventResponse {
+		self.state.on_window_event(window, event)
+	}
+
+	fn handle_resized(&mut self, width: u32, height: u32) {
+		if width > 0 && height > 0 {
+			match new_pixmap(width, height) {
+				Ok(pixmap) => self.pixmap = pixmap,
+				Err(e) => tracing::error!("Failed to resize software surface: {e}"),
+			}
+		}
+	}
+
+	fn handle_redraw(
+		&mut self,
+		window: &Arc<Window>,
+		egui_view: &mut dyn EguiView,
+	) -> anyhow::Result<()> {
+		let raw_input = self.state.take_egui_input(window);
+
+		let ctx = self.state.egui_ctx().clone();
+		let full_output = ctx.run(raw_input, |ctx| egui_view.update(ctx));
+
+		self.state
+			.handle_platform_output(window, full_output.platform_output);
+
+		for (id, image_delta) in &full_output.textures_delta.set {
+			self.rasterizer.update_texture(*id, image_delta);
+		}
+
+		let primitives = ctx.tessellate(full_output.shapes, full_output.pixels_per_point);
+
+		self.pixmap.fill(tiny_skia::Color::TRANSPARENT);
+		self.rasterizer
+			.render(&mut self.pixmap, &primitives, full_output.pixels_per_point);
+
+		for id in &full_output.textures_delta.free {
+			self.rasterizer.free_texture(id);
+		}
+
+		self.present()
+	}
+
+	fn recreate(&mut self, window: &Arc<Window>, _backend: &Backend) -> anyhow::Result<()> {
+		self.state = create_egui_state(window);
+		self.rasterizer = Rasterizer::default();
+		Ok(())
+	}
+}
+
+fn new_pixmap(width: u32, height: u32) -> anyhow::Result<Pixmap> {
+	Pixmap::new(width.max(1), height.max(1))
+		.ok_or_else(|| anyhow::anyhow!("Invalid surface size {width}x{height}"))
+}
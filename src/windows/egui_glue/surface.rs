@@ -0,0 +1,42 @@
+use std::sync::Arc;
+
+use winit::event::WindowEvent;
+use winit::window::Window;
+
+use super::{Backend, EguiView};
+
+/// A window surface egui is rendered to, see [`Backend::create_surface`].
+pub trait Surface {
+	fn egui_ctx(&self) -> &egui::Context;
+
+	fn handle_input(&mut self, window: &Window, event: &WindowEvent) -> egui_winit::EventResponse;
+
+	fn handle_resized(&mut self, width: u32, height: u32);
+
+	fn handle_redraw(
+		&mut self,
+		window: &Arc<Window>,
+		egui_view: &mut dyn EguiView,
+	) -> anyhow::Result<()>;
+
+	/// Recreates the surface with a new backend, i.e after the GPU device was lost.
+	///
+	/// This also creates a new egui context, as the textures uploaded by the
+	/// old one are gone with the old backend.
+	fn recreate(&mut self, window: &Arc<Window>, backend: &Backend) -> anyhow::Result<()>;
+}
+
+/// Creates the egui context and input state of a window,
+/// see [`RepaintScheduler`](super::RepaintScheduler) for how repaints are requested.
+pub fn create_egui_state(window: &Arc<Window>) -> egui_winit::State {
+	let egui_context = egui::Context::default();
+
+	egui_winit::State::new(
+		egui_context,
+		egui::viewport::ViewportId::ROOT,
+		&window,
+		Some(window.scale_factor() as f32),
+		window.theme(),
+		Some(2 * 1024), // default dimension is 2048
+	)
+}
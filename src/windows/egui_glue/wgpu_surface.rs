@@ -7,53 +7,49 @@ use winit::window::Window;
 
 use super::dx12_surface::Dx12Surface;
 use super::egui_renderer::EguiRenderer;
-use super::EguiView;
+use super::surface::Surface;
+use super::{Backend, EguiView, GpuContext};
 
 pub struct WgpuSurface {
-	device: wgpu::Device,
-	queue: wgpu::Queue,
+	gpu: Arc<GpuContext>,
 	surface_config: wgpu::SurfaceConfiguration,
 	surface: wgpu::Surface<'static>,
-	pub egui_renderer: EguiRenderer,
-	#[allow(unused)]
+	egui_renderer: EguiRenderer,
 	dx12_surface: Dx12Surface,
 }
 
 impl WgpuSurface {
-	pub async fn new(window: &Arc<Window>, instance: &wgpu::Instance) -> anyhow::Result<Self> {
+	pub fn new(window: &Arc<Window>, gpu: &Arc<GpuContext>) -> anyhow::Result<Self> {
 		let dx12_surface = Dx12Surface::new(window)?;
 
+		let (surface, surface_config) = Self::create_surface(window, gpu, &dx12_surface)?;
+
+		let egui_renderer = EguiRenderer::new(&gpu.device, surface_config.format, None, 1, window);
+
+		Ok(Self {
+			gpu: gpu.clone(),
+			surface,
+			surface_config,
+			egui_renderer,
+			dx12_surface,
+		})
+	}
+
+	/// Creates and configures a wgpu surface for the composition visual.
+	fn create_surface(
+		window: &Window,
+		gpu: &GpuContext,
+		dx12_surface: &Dx12Surface,
+	) -> anyhow::Result<(wgpu::Surface<'static>, wgpu::SurfaceConfiguration)> {
 		let visual = dx12_surface.wgpu_visual.as_raw();
 		let visual = wgpu::SurfaceTargetUnsafe::CompositionVisual(visual);
-		let surface = unsafe { instance.create_surface_unsafe(visual)? };
-
-		let power_pref = wgpu::PowerPreference::LowPower;
-		let adapter = instance
-			.request_adapter(&wgpu::RequestAdapterOptions {
-				power_preference: power_pref,
-				force_fallback_adapter: false,
-				compatible_surface: Some(&surface),
-			})
-			.await
-			.context("Failed to find an appropriate adapter")?;
+		let surface = unsafe { gpu.instance.create_surface_unsafe(visual)? };
 
 		let size = window.inner_size();
 		let width = size.width.max(1);
 		let height = size.height.max(1);
 
-		let (device, queue) = adapter
-			.request_device(
-				&wgpu::DeviceDescriptor {
-					label: None,
-					required_features: wgpu::Features::empty(),
-					required_limits: wgpu::Limits::default(),
-					memory_hints: wgpu::MemoryHints::MemoryUsage,
-				},
-				None,
-			)
-			.await?;
-
-		let swapchain_capabilities = surface.get_capabilities(&adapter);
+		let swapchain_capabilities = surface.get_capabilities(&gpu.adapter);
 		let selected_format = wgpu::TextureFormat::Bgra8UnormSrgb;
 		let swapchain_format = swapchain_capabilities
 			.formats
@@ -72,59 +68,65 @@ impl WgpuSurface {
 			view_formats: vec![],
 		};
 
-		surface.configure(&device, &surface_config);
+		surface.configure(&gpu.device, &surface_config);
 
 		unsafe { dx12_surface.desktop.Commit()? };
 
-		let egui_renderer = EguiRenderer::new(&device, surface_config.format, None, 1, window);
-
-		Ok(Self {
-			device,
-			queue,
-			surface,
-			surface_config,
-			egui_renderer,
-			dx12_surface,
-		})
+		Ok((surface, surface_config))
 	}
 
 	pub fn resize_surface(&mut self, width: u32, height: u32) {
 		self.surface_config.width = width;
 		self.surface_config.height = height;
-		self.surface.configure(&self.device, &self.surface_config);
+		self.surface
+			.configure(&self.gpu.device, &self.surface_config);
 	}
+}
 
-	pub fn handle_input(
-		&mut self,
-		window: &Window,
-		event: &WindowEvent,
-	) -> egui_winit::EventResponse {
+impl Surface for WgpuSurface {
+	fn egui_ctx(&self) -> &egui::Context {
+		self.egui_renderer.egui_ctx()
+	}
+
+	fn handle_input(&mut self, window: &Window, event: &WindowEvent) -> egui_winit::EventResponse {
 		self.egui_renderer.state.on_window_event(window, event)
 	}
 
-	pub fn handle_resized(&mut self, width: u32, height: u32) {
+	fn handle_resized(&mut self, width: u32, height: u32) {
 		if width > 0 && height > 0 {
 			self.resize_surface(width, height);
 		}
 	}
 
-	pub fn handle_redraw(
+	fn handle_redraw(
 		&mut self,
 		window: &Arc<Window>,
-		egui_view: &mut (impl EguiView + ?Sized),
+		egui_view: &mut dyn EguiView,
 	) -> anyhow::Result<()> {
 		let screen_descriptor = egui_wgpu::ScreenDescriptor {
 			size_in_pixels: [self.surface_config.width, self.surface_config.height],
 			pixels_per_point: self.egui_renderer.egui_ctx().pixels_per_point(),
 		};
 
-		let surface_texture = self.surface.get_current_texture()?;
+		let surface_texture = match self.surface.get_current_texture() {
+			Ok(texture) => texture,
+			// Reconfigure and skip this frame, the next one will be drawn normally
+			Err(wgpu::SurfaceError::Lost | wgpu::SurfaceError::Outdated) => {
+				tracing::warn!("Surface lost or outdated, reconfiguring");
+				self.surface
+					.configure(&self.gpu.device, &self.surface_config);
+				window.request_redraw();
+				return Ok(());
+			}
+			Err(e) => return Err(e.into()),
+		};
 
 		let surface_view = surface_texture
 			.texture
 			.create_view(&wgpu::TextureViewDescriptor::default());
 
 		let mut encoder = self
+			.gpu
 			.device
 			.create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
 
@@ -136,8 +138,8 @@ impl WgpuSurface {
 			egui_view.update(ctx);
 
 			self.egui_renderer.end_frame_and_draw(
-				&self.device,
-				&self.queue,
+				&self.gpu.device,
+				&self.gpu.queue,
 				&mut encoder,
 				window,
 				&surface_view,
@@ -145,9 +147,26 @@ impl WgpuSurface {
 			);
 		}
 
-		self.queue.submit(Some(encoder.finish()));
+		self.gpu.queue.submit(Some(encoder.finish()));
 		surface_texture.present();
 
 		Ok(())
 	}
+
+	fn recreate(&mut self, window: &Arc<Window>, backend: &Backend) -> anyhow::Result<()> {
+		let Backend::Gpu(gpu) = backend else {
+			anyhow::bail!("Can't recreate a GPU surface without a GPU backend");
+		};
+
+		// Reuse the composition visual, as only one composition target can be
+		// created for a window.
+		let (surface, surface_config) = Self::create_surface(window, gpu, &self.dx12_surface)?;
+
+		self.egui_renderer = EguiRenderer::new(&gpu.device, surface_config.format, None, 1, window);
+		self.surface = surface;
+		self.surface_config = surface_config;
+		self.gpu = gpu.clone();
+
+		Ok(())
+	}
 }
@@ -4,13 +4,13 @@ use crate::windows::app::{App, AppMessage};
 
 mod app;
 mod context_menu;
+mod diagnostics;
 mod egui_glue;
 mod message_window;
 mod registry;
 mod taskbar;
 mod tray_icon;
 mod utils;
-mod widgets;
 #[allow(clippy::module_inception)]
 mod windows;
 
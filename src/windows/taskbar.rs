@@ -1,16 +1,119 @@
 use windows::Win32::Foundation::*;
+use windows::Win32::Graphics::Gdi::{
+	GetMonitorInfoW, IntersectRect, MonitorFromWindow, MONITORINFO, MONITOR_DEFAULTTONEAREST,
+};
+use windows::Win32::UI::HiDpi::GetDpiForWindow;
+use windows::Win32::UI::Shell::{SHAppBarMessage, ABM_GETSTATE, ABS_AUTOHIDE, APPBARDATA};
 use windows::Win32::UI::WindowsAndMessaging::*;
 
+use crate::config::{Anchor, MonitorConfig};
 use crate::windows::utils;
 
 #[derive(Debug, Clone, Copy)]
 pub struct Taskbar {
 	pub hwnd: HWND,
 	pub rect: RECT,
+	/// Rect of the monitor the taskbar is on, used to match it with komorebi
+	/// monitors even when it is auto-hidden and mostly off screen.
+	pub monitor_rect: RECT,
+}
+
+/// Whether taskbars are set to auto-hide, which applies to all taskbars.
+pub fn is_auto_hide() -> bool {
+	let mut data = APPBARDATA {
+		cbSize: std::mem::size_of::<APPBARDATA>() as u32,
+		..Default::default()
+	};
+	let state = unsafe { SHAppBarMessage(ABM_GETSTATE, &mut data) };
+	(state as u32 & ABS_AUTOHIDE) != 0
+}
+
+/// Whether the taskbar is auto-hidden and less than half of it is visible
+/// on its monitor.
+fn is_hidden(taskbar: HWND) -> bool {
+	if !is_auto_hide() {
+		return false;
+	}
+
+	let mut rect = RECT::default();
+	if unsafe { GetWindowRect(taskbar, &mut rect) }.is_err() {
+		return false;
+	}
+	let Some(monitor) = monitor_rect(taskbar) else {
+		return false;
+	};
+
+	let mut visible = RECT::default();
+	let _ = unsafe { IntersectRect(&mut visible, &rect, &monitor) };
+
+	if TaskbarEdge::of(taskbar).is_vertical() {
+		(visible.right - visible.left) < (rect.right - rect.left) / 2
+	} else {
+		(visible.bottom - visible.top) < (rect.bottom - rect.top) / 2
+	}
+}
+
+/// Gets the rect of the monitor the window is on.
+fn monitor_rect(hwnd: HWND) -> Option<RECT> {
+	let monitor = unsafe { MonitorFromWindow(hwnd, MONITOR_DEFAULTTONEAREST) };
+	let mut info = MONITORINFO {
+		cbSize: std::mem::size_of::<MONITORINFO>() as u32,
+		..Default::default()
+	};
+	unsafe { GetMonitorInfoW(monitor, &mut info) }
+		.as_bool()
+		.then_some(info.rcMonitor)
+}
+
+/// Screen edge a taskbar is docked to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TaskbarEdge {
+	Left,
+	Top,
+	Right,
+	#[default]
+	Bottom,
+}
+
+impl TaskbarEdge {
+	/// Detects the edge by comparing the taskbar rect to its monitor rect,
+	/// which also works for auto-hidden taskbars and taskbars moved by third
+	/// party tools like ExplorerPatcher or StartAllBack.
+	fn of(taskbar: HWND) -> Self {
+		let mut rect = RECT::default();
+		if unsafe { GetWindowRect(taskbar, &mut rect) }.is_err() {
+			return Self::default();
+		}
+
+		let Some(monitor) = monitor_rect(taskbar) else {
+			return Self::default();
+		};
+
+		let width = rect.right - rect.left;
+		let height = rect.bottom - rect.top;
+
+		if width >= height {
+			if rect.top <= monitor.top && rect.bottom < monitor.bottom {
+				Self::Top
+			} else {
+				Self::Bottom
+			}
+		} else if rect.left <= monitor.left && rect.right < monitor.right {
+			Self::Left
+		} else {
+			Self::Right
+		}
+	}
+
+	pub fn is_vertical(&self) -> bool {
+		matches!(self, Self::Left | Self::Right)
+	}
 }
 
 pub const TASKBAR_CLASS_NAME: &str = "Shell_TrayWnd";
 pub const TASKBAR_SECONDARY_CLASS_NAME: &str = "Shell_SecondaryTrayWnd";
+const START_BUTTON_CLASS_NAME: &str = "Start";
+const TRAY_CLASS_NAME: &str = "TrayNotifyWnd";
 
 fn is_taskbar(hwnd: HWND) -> bool {
 	let class_name = utils::get_class_name(hwnd);
@@ -25,10 +128,157 @@ pub fn all() -> Vec<Taskbar> {
 			if is_taskbar(hwnd) {
 				let mut rect = Default::default();
 				unsafe { GetWindowRect(hwnd, &mut rect) }.ok()?;
-				Some(Taskbar { hwnd, rect })
+				let monitor_rect = monitor_rect(hwnd).unwrap_or(rect);
+				Some(Taskbar {
+					hwnd,
+					rect,
+					monitor_rect,
+				})
 			} else {
 				None
 			}
 		})
 		.collect()
 }
+
+/// Taskbar elements the switcher can be anchored to,
+/// with rects relative to the taskbar client area.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TaskbarLayout {
+	pub client: RECT,
+	/// The Start button, not available on Windows 11 where it is part of the
+	/// XAML content of the taskbar.
+	pub start_button: Option<RECT>,
+	/// The system tray, only available on the primary taskbar.
+	pub tray: Option<RECT>,
+	pub edge: TaskbarEdge,
+	/// Whether the taskbar is auto-hidden and currently hidden.
+	pub hidden: bool,
+	pub dpi: u32,
+}
+
+impl TaskbarLayout {
+	/// Inspects the child windows of the taskbar to find its elements.
+	pub fn new(taskbar: HWND) -> anyhow::Result<Self> {
+		let mut client = RECT::default();
+		unsafe { GetClientRect(taskbar, &mut client) }?;
+
+		let mut layout = Self {
+			client,
+			edge: TaskbarEdge::of(taskbar),
+			hidden: is_hidden(taskbar),
+			dpi: unsafe { GetDpiForWindow(taskbar) },
+			..Default::default()
+		};
+
+		for child in utils::enum_child_windows(taskbar) {
+			if !unsafe { IsWindowVisible(child) }.as_bool() {
+				continue;
+			}
+
+			let class_name = utils::get_class_name(child);
+			let rect = match class_name.as_str() {
+				START_BUTTON_CLASS_NAME => &mut layout.start_button,
+				TRAY_CLASS_NAME => &mut layout.tray,
+				_ => continue,
+			};
+
+			if rect.is_none() {
+				*rect = utils::rect_in_parent(Some(taskbar), child);
+			}
+		}
+
+		Ok(layout)
+	}
+
+	/// Computes the position of a host of the given size in the taskbar,
+	/// resolving the anchor of the monitor config.
+	///
+	/// On vertical taskbars, anchors apply to the vertical axis instead,
+	/// i.e `left` is the top edge and `right` is the bottom edge.
+	pub fn host_position(
+		&self,
+		monitor_config: &MonitorConfig,
+		width: i32,
+		height: i32,
+	) -> (i32, i32) {
+		if monitor_config.anchor == Anchor::Absolute {
+			return (monitor_config.x, monitor_config.y);
+		}
+
+		// Offsets are in logical pixels
+		let scale = self.scale_factor();
+		let offset_x = (monitor_config.x as f32 * scale).round() as i32;
+		let offset_y = (monitor_config.y as f32 * scale).round() as i32;
+
+		let (x, y) = self.anchor_origin(monitor_config.anchor, width, height);
+		(x + offset_x, y + offset_y)
+	}
+
+	/// Computes the `x` and `y` of the monitor config that place a host of
+	/// the given size at the given position, the inverse of [`Self::host_position`].
+	pub fn host_offset(
+		&self,
+		monitor_config: &MonitorConfig,
+		(x, y): (i32, i32),
+		width: i32,
+		height: i32,
+	) -> (i32, i32) {
+		if monitor_config.anchor == Anchor::Absolute {
+			return (x, y);
+		}
+
+		let scale = self.scale_factor();
+		let (origin_x, origin_y) = self.anchor_origin(monitor_config.anchor, width, height);
+		let offset_x = ((x - origin_x) as f32 / scale).round() as i32;
+		let offset_y = ((y - origin_y) as f32 / scale).round() as i32;
+		(offset_x, offset_y)
+	}
+
+	/// Gets the position of the anchor along the main axis of the taskbar,
+	/// the horizontal axis or the vertical one for vertical taskbars.
+	pub fn anchor_position(&self, anchor: Anchor, width: i32, height: i32) -> i32 {
+		if self.edge.is_vertical() {
+			self.anchored(anchor, (self.client.top, self.client.bottom), height, |r| {
+				(r.top, r.bottom)
+			})
+		} else {
+			self.anchored(anchor, (self.client.left, self.client.right), width, |r| {
+				(r.left, r.right)
+			})
+		}
+	}
+
+	/// Gets the position of a host of the given size placed at the anchor without offsets.
+	fn anchor_origin(&self, anchor: Anchor, width: i32, height: i32) -> (i32, i32) {
+		let position = self.anchor_position(anchor, width, height);
+		if self.edge.is_vertical() {
+			(self.client.left, position)
+		} else {
+			(position, self.client.top)
+		}
+	}
+
+	/// Gets the scale factor of the taskbar, used to convert logical pixels.
+	pub fn scale_factor(&self) -> f32 {
+		self.dpi as f32 / USER_DEFAULT_SCREEN_DPI as f32
+	}
+
+	/// Resolves an anchor along one axis, given the start and end of the
+	/// taskbar and of its elements on that axis.
+	fn anchored(
+		&self,
+		anchor: Anchor,
+		(start, end): (i32, i32),
+		size: i32,
+		axis: impl Fn(&RECT) -> (i32, i32),
+	) -> i32 {
+		match anchor {
+			Anchor::Absolute | Anchor::Left => start,
+			Anchor::Center => start + (end - start - size) / 2,
+			Anchor::Right => end - size,
+			Anchor::AfterStart => self.start_button.map_or(start, |r| axis(&r).1),
+			Anchor::BeforeTray => self.tray.map_or(end - size, |r| axis(&r).0 - size),
+		}
+	}
+}
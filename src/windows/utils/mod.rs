@@ -5,7 +5,11 @@ pub use hwnd::HwndWithDrop;
 pub use multi_map::MultiMap;
 use windows::core::*;
 use windows::Win32::Foundation::*;
+use windows::Win32::Graphics::Gdi::{
+	MapWindowPoints, MonitorFromPoint, MonitorFromWindow, MONITOR_DEFAULTTONEAREST,
+};
 use windows::Win32::UI::WindowsAndMessaging::*;
+use windows::UI::ViewManagement::{UIColorType, UISettings};
 
 pub fn enum_child_windows(hwnd: HWND) -> Vec<HWND> {
 	let mut children = Vec::new();
@@ -24,6 +28,52 @@ pub fn enum_child_windows(hwnd: HWND) -> Vec<HWND> {
 	children
 }
 
+/// Gets the rect of a child window relative to the client area of its parent,
+/// or in screen coordinates if there is no parent.
+pub fn rect_in_parent(parent: Option<HWND>, child: HWND) -> Option<RECT> {
+	let mut rect = RECT::default();
+	unsafe { GetWindowRect(child, &mut rect) }.ok()?;
+
+	let mut points = [
+		POINT {
+			x: rect.left,
+			y: rect.top,
+		},
+		POINT {
+			x: rect.right,
+			y: rect.bottom,
+		},
+	];
+	unsafe { MapWindowPoints(None, parent, &mut points) };
+
+	Some(RECT {
+		left: points[0].x,
+		top: points[0].y,
+		right: points[1].x,
+		bottom: points[1].y,
+	})
+}
+
+/// Gets the handle of the monitor the cursor is on.
+pub fn cursor_monitor() -> isize {
+	let mut point = POINT::default();
+	let _ = unsafe { GetCursorPos(&mut point) };
+	let monitor = unsafe { MonitorFromPoint(point, MONITOR_DEFAULTTONEAREST) };
+	monitor.0 as isize
+}
+
+/// Gets the handle of the monitor the window is on.
+pub fn window_monitor(hwnd: HWND) -> isize {
+	let monitor = unsafe { MonitorFromWindow(hwnd, MONITOR_DEFAULTTONEAREST) };
+	monitor.0 as isize
+}
+
+/// Whether the system is in dark mode, which uses a white foreground color.
+pub fn is_system_dark_mode() -> bool {
+	let foreground = UISettings::new().and_then(|s| s.GetColorValue(UIColorType::Foreground));
+	foreground.is_ok_and(|c| (c.R, c.G, c.B) == (255, 255, 255))
+}
+
 pub fn get_class_name(hwnd: HWND) -> String {
 	let mut buffer: [u16; 256] = [0; 256];
 	let len = unsafe { GetClassNameW(hwnd, &mut buffer) };
@@ -60,13 +110,3 @@ impl Iterator for TopLevelWindowsIterator {
 		}
 	}
 }
-
-pub fn egui_color_from_color(color: &str) -> Option<egui::Color32> {
-	let rgba = color::parse_color(color)
-		.ok()?
-		.to_alpha_color::<color::Srgb>()
-		.to_rgba8();
-	Some(egui::Color32::from_rgba_unmultiplied(
-		rgba.r, rgba.g, rgba.b, rgba.a,
-	))
-}
@@ -1,5 +0,0 @@
-mod layout_button;
-mod workspace_button;
-
-pub use layout_button::*;
-pub use workspace_button::*;
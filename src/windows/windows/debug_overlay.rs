@@ -0,0 +1,224 @@
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use windows::Win32::Foundation::{HWND, RECT};
+use winit::dpi::PhysicalSize;
+use winit::event::WindowEvent;
+use winit::event_loop::ActiveEventLoop;
+use winit::platform::windows::WindowAttributesExtWindows;
+use winit::window::{WindowAttributes, WindowLevel};
+
+use crate::windows::app::App;
+use crate::windows::egui_glue::{EguiView, EguiWindow, FrameStats};
+
+/// Interval at which the overlay is refreshed while shown.
+const REFRESH_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Diagnostics published by a switcher while the debug overlay is shown.
+#[derive(Debug, Clone)]
+pub struct SwitcherDiagnostics {
+	/// Name and id of the monitor.
+	pub monitor: String,
+	pub host: (HWND, RECT),
+	/// The taskbar hosting the switcher, `None` for floating switchers.
+	pub taskbar: Option<(HWND, RECT)>,
+	pub pixels_per_point: f32,
+	pub frame_stats: FrameStats,
+	/// The resolved monitor config, serialized to TOML.
+	pub monitor_config: String,
+}
+
+#[derive(Debug, Default)]
+struct DebugOverlayState {
+	visible: bool,
+	/// Diagnostics of each switcher, by monitor id.
+	switchers: BTreeMap<String, SwitcherDiagnostics>,
+}
+
+/// Shared state of the debug overlay, which shows runtime diagnostics of
+/// the komorebi connection and of all switchers.
+#[derive(Debug, Clone, Default)]
+pub struct DebugOverlay(Arc<Mutex<DebugOverlayState>>);
+
+impl DebugOverlay {
+	pub fn is_visible(&self) -> bool {
+		self.0.lock().unwrap().visible
+	}
+
+	fn set_visible(&self, visible: bool) {
+		let mut state = self.0.lock().unwrap();
+		state.visible = visible;
+		if !visible {
+			state.switchers.clear();
+		}
+	}
+
+	pub fn publish(&self, monitor_id: &str, diagnostics: SwitcherDiagnostics) {
+		let mut state = self.0.lock().unwrap();
+		state.switchers.insert(monitor_id.to_string(), diagnostics);
+	}
+
+	/// Removes the diagnostics of a switcher that was closed.
+	pub fn remove(&self, monitor_id: &str) {
+		self.0.lock().unwrap().switchers.remove(monitor_id);
+	}
+}
+
+impl App {
+	/// Shows the debug overlay window, or closes it if already shown.
+	pub fn toggle_debug_overlay(&mut self, event_loop: &ActiveEventLoop) -> anyhow::Result<()> {
+		// The window may have been closed from its title bar
+		if let Some(window_id) = self.debug_overlay_window.take() {
+			if self.windows.remove(&window_id).is_some() {
+				self.debug_overlay.set_visible(false);
+				return Ok(());
+			}
+		}
+
+		#[cfg(debug_assertions)]
+		let class_name = "komorebi-switcher-debug::debug-overlay";
+		#[cfg(not(debug_assertions))]
+		let class_name = "komorebi-switcher::debug-overlay";
+
+		let attrs = WindowAttributes::default()
+			.with_title("Debug overlay")
+			.with_class_name(class_name)
+			.with_inner_size(PhysicalSize::new(420, 600))
+			.with_resizable(true)
+			.with_active(false)
+			.with_window_level(WindowLevel::AlwaysOnTop)
+			.with_no_redirection_bitmap(self.backend.no_redirection_bitmap());
+
+		let window = event_loop.create_window(attrs)?;
+		let window = Arc::new(window);
+
+		let view = DebugOverlayView {
+			overlay: self.debug_overlay.clone(),
+		};
+
+		let window = EguiWindow::new(window, &self.backend, view)?;
+
+		self.debug_overlay.set_visible(true);
+		self.debug_overlay_window = Some(window.id());
+		self.windows.insert(window.id(), None, window);
+
+		Ok(())
+	}
+}
+
+struct DebugOverlayView {
+	overlay: DebugOverlay,
+}
+
+impl DebugOverlayView {
+	fn komorebi_ui(&self, ui: &mut egui::Ui) {
+		let stats = crate::komorebi::listener_stats();
+
+		ui.heading("Komorebi");
+
+		egui::Grid::new("komorebi").num_columns(2).show(ui, |ui| {
+			ui.label("Connected");
+			ui.label(if stats.connected { "Yes" } else { "No" });
+			ui.end_row();
+
+			ui.label("Last event");
+			match &stats.last_event {
+				Some((event, at)) => {
+					ui.label(format!("{event} ({:.1}s ago)", at.elapsed().as_secs_f32()))
+				}
+				None => ui.label("None"),
+			};
+			ui.end_row();
+
+			ui.label("Notifications");
+			ui.label(format!(
+				"{} ({:.1}/s)",
+				stats.notifications,
+				stats.notification_rate()
+			));
+			ui.end_row();
+
+			ui.label("Parse errors");
+			ui.label(stats.parse_errors.to_string());
+			ui.end_row();
+		});
+	}
+
+	fn switchers_ui(&self, ui: &mut egui::Ui) {
+		let state = self.overlay.0.lock().unwrap();
+
+		for (monitor_id, switcher) in &state.switchers {
+			ui.separator();
+			ui.heading(&switcher.monitor);
+
+			egui::Grid::new(monitor_id).num_columns(2).show(ui, |ui| {
+				let (host, rect) = switcher.host;
+				ui.label("Host");
+				ui.label(format_window(host, rect));
+				ui.end_row();
+
+				ui.label("Taskbar");
+				match switcher.taskbar {
+					Some((taskbar, rect)) => ui.label(format_window(taskbar, rect)),
+					None => ui.label("None (floating)"),
+				};
+				ui.end_row();
+
+				ui.label("Pixels per point");
+				ui.label(switcher.pixels_per_point.to_string());
+				ui.end_row();
+
+				let stats = switcher.frame_stats;
+				ui.label("Frame time");
+				ui.label(format!(
+					"{:.2} ms ({} fps)",
+					stats.frame_time.as_secs_f64() * 1000.0,
+					stats.fps
+				));
+				ui.end_row();
+			});
+
+			egui::CollapsingHeader::new("Monitor config")
+				.id_salt(("monitor_config", monitor_id))
+				.show(ui, |ui| ui.monospace(&switcher.monitor_config));
+		}
+	}
+}
+
+impl EguiView for DebugOverlayView {
+	fn handle_window_event(
+		&mut self,
+		_ctx: &egui::Context,
+		_event_loop: &ActiveEventLoop,
+		event: WindowEvent,
+	) -> anyhow::Result<()> {
+		if event == WindowEvent::CloseRequested {
+			self.overlay.set_visible(false);
+		}
+
+		Ok(())
+	}
+
+	fn update(&mut self, ctx: &egui::Context) {
+		ctx.request_repaint_after(REFRESH_INTERVAL);
+
+		egui::CentralPanel::default().show(ctx, |ui| {
+			egui::ScrollArea::vertical().show(ui, |ui| {
+				self.komorebi_ui(ui);
+				self.switchers_ui(ui);
+			});
+		});
+	}
+}
+
+fn format_window(hwnd: HWND, rect: RECT) -> String {
+	format!(
+		"{:#x} at ({}, {}), {}x{}",
+		hwnd.0 as usize,
+		rect.left,
+		rect.top,
+		rect.right - rect.left,
+		rect.bottom - rect.top
+	)
+}
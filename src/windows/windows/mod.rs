@@ -1,2 +1,7 @@
+mod debug_overlay;
 mod settings;
 mod switcher;
+mod toasts;
+
+pub use debug_overlay::DebugOverlay;
+pub use toasts::Toasts;
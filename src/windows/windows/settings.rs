@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::sync::{Arc, RwLock};
 
 use winit::dpi::PhysicalSize;
@@ -5,11 +6,20 @@ use winit::event_loop::{ActiveEventLoop, EventLoopProxy};
 use winit::platform::windows::WindowAttributesExtWindows;
 use winit::window::{WindowAttributes, WindowId};
 
-use crate::config::Config;
+use crate::config::{
+	Anchor, Config, Easing, FontFamily, IndicatorStyle, LogFormat, Renderer,
+	DEFAULT_KOMOREBI_START_COMMAND, FONT_SIZE_RANGE,
+};
 use crate::komorebi::State;
+use crate::theme::DEFAULT_THEME;
 use crate::windows::app::{App, AppMessage};
 use crate::windows::egui_glue::{EguiView, EguiWindow};
 
+const FONT_FAMILY_HINT: &str = "i.e JetBrainsMono Nerd Font, Segoe UI Emoji";
+const LOG_LEVEL_HINT: &str = "i.e info or info,komorebi_switcher=trace";
+const DEFAULT_FONT_SIZE: f32 = 14.0;
+const DEFAULT_ANIMATION_DURATION: u32 = 200;
+
 impl App {
 	pub fn create_settings_window(&mut self, event_loop: &ActiveEventLoop) -> anyhow::Result<()> {
 		#[cfg(debug_assertions)]
@@ -22,7 +32,7 @@ impl App {
 			.with_class_name(class_name)
 			.with_inner_size(PhysicalSize::new(500, 600))
 			.with_resizable(true)
-			.with_no_redirection_bitmap(true);
+			.with_no_redirection_bitmap(self.backend.no_redirection_bitmap());
 
 		let window = event_loop.create_window(attrs)?;
 		let window = Arc::new(window);
@@ -33,9 +43,12 @@ impl App {
 			config_: self.config.clone(),
 			config: self.config.read().unwrap().clone(),
 			komorebi_state: self.komorebi_state.clone(),
+			themes: crate::theme::available_themes(),
+			previewed_config: None,
+			font_family_texts: HashMap::new(),
 		};
 
-		let window = EguiWindow::new(window, &self.wgpu_instance, state)?;
+		let window = EguiWindow::new(window, &self.backend, state)?;
 
 		self.windows.insert(window.id(), None, window);
 
@@ -49,6 +62,13 @@ struct SettingsWindowView {
 	config_: Arc<RwLock<Config>>,
 	config: Config,
 	komorebi_state: State,
+	/// Names of the available themes, listed once when the window is created.
+	themes: Vec<String>,
+	/// Last config sent for preview, to only send it again when it changes.
+	previewed_config: Option<Config>,
+	/// Font family fields as typed, by monitor id or `None` for the global one,
+	/// as formatting the parsed families back would drop separators mid-typing.
+	font_family_texts: HashMap<Option<String>, String>,
 }
 
 impl SettingsWindowView {
@@ -59,10 +79,24 @@ impl SettingsWindowView {
 
 	fn save(&mut self) -> anyhow::Result<()> {
 		let mut config = self.config_.write().unwrap();
+
+		// Switching between taskbar and floating switchers needs new host windows
+		let recreate =
+			self.config.monitors.iter().any(|(id, monitor_config)| {
+				monitor_config.floating != config.get_monitor(id).floating
+			});
+
+		if self.config.logging.level != config.logging.level {
+			crate::logging::set_level(&self.config.logging.level);
+		}
+
 		*config = self.config.clone();
 		config.save()?;
 
 		self.proxy.send_event(AppMessage::ClearPreviewConfig)?;
+		if recreate {
+			self.proxy.send_event(AppMessage::RecreateSwitcherWindows)?;
+		}
 		self.close_window()
 	}
 
@@ -71,11 +105,17 @@ impl SettingsWindowView {
 		self.close_window()
 	}
 
-	fn preview_config(&self) {
+	fn preview_config(&mut self) {
+		if self.previewed_config.as_ref() == Some(&self.config) {
+			return;
+		}
+
 		let message = AppMessage::PreviewConfig(self.config.clone());
 		if let Err(e) = self.proxy.send_event(message) {
 			tracing::error!("Failed to send preview config: {e}");
 		}
+
+		self.previewed_config = Some(self.config.clone());
 	}
 
 	fn global_show_layout_button_ui(&mut self, ui: &mut egui::Ui) {
@@ -92,13 +132,23 @@ impl SettingsWindowView {
 		));
 	}
 
+	fn global_sync_komorebi_theme_ui(&mut self, ui: &mut egui::Ui) {
+		ui.add(egui::Checkbox::new(
+			&mut self.config.sync_komorebi_theme,
+			"Sync komorebi theme",
+		));
+	}
+
 	fn global_font_family_ui(&mut self, ui: &mut egui::Ui) {
 		ui.label("Font Family");
 
-		let mut font_family = self.config.font_family.clone().unwrap_or_default();
-		let text_edit = egui::TextEdit::singleline(&mut font_family).hint_text("i.e Roboto");
+		let font_family = self.font_family_texts.entry(None).or_insert_with(|| {
+			let font_family = self.config.font_family.as_ref();
+			font_family.map(ToString::to_string).unwrap_or_default()
+		});
+		let text_edit = egui::TextEdit::singleline(font_family).hint_text(FONT_FAMILY_HINT);
 		if ui.add(text_edit).changed() {
-			self.config.font_family = (!font_family.is_empty()).then_some(font_family);
+			self.config.font_family = FontFamily::from_text(font_family);
 		}
 	}
 
@@ -113,6 +163,152 @@ impl SettingsWindowView {
 		}
 	}
 
+	fn global_font_size_ui(&mut self, ui: &mut egui::Ui) {
+		ui.label("Font Size");
+
+		let mut font_size = self.config.font_size.unwrap_or(DEFAULT_FONT_SIZE);
+		let drag_value = egui::DragValue::new(&mut font_size);
+		let drag_value = drag_value.range(FONT_SIZE_RANGE).speed(0.5);
+		if ui.add(drag_value).changed() {
+			self.config.font_size = Some(font_size);
+		}
+	}
+
+	fn global_label_format_ui(&mut self, ui: &mut egui::Ui) {
+		ui.label("Label Format");
+
+		let mut label_format = self.config.label_format.clone().unwrap_or_default();
+		let text_edit =
+			egui::TextEdit::singleline(&mut label_format).hint_text("i.e {index}: {name}");
+		if ui.add(text_edit).changed() {
+			self.config.label_format = (!label_format.is_empty()).then_some(label_format);
+		}
+	}
+
+	fn global_indicator_style_ui(&mut self, ui: &mut egui::Ui) {
+		ui.label("Indicator Style");
+
+		let selected = &mut self.config.indicator_style;
+		egui::ComboBox::new("global_indicator_style", "")
+			.selected_text(format!("{}", selected))
+			.show_ui(ui, |ui| {
+				for option in IndicatorStyle::ALL {
+					ui.selectable_value(selected, option, format!("{}", option));
+				}
+			});
+	}
+
+	fn global_sliding_indicator_ui(&mut self, ui: &mut egui::Ui) {
+		ui.add(egui::Checkbox::new(
+			&mut self.config.animations.sliding_indicator,
+			"Sliding indicator",
+		));
+	}
+
+	fn global_reduce_motion_ui(&mut self, ui: &mut egui::Ui) {
+		ui.add(egui::Checkbox::new(
+			&mut self.config.animations.reduce_motion,
+			"Reduce motion",
+		));
+	}
+
+	fn global_animation_duration_ui(&mut self, ui: &mut egui::Ui) {
+		ui.label("Animation Duration (ms)");
+
+		ui.horizontal(|ui| {
+			let animations = &mut self.config.animations;
+			let mut duration = animations.duration.unwrap_or(DEFAULT_ANIMATION_DURATION);
+			let drag_value = egui::DragValue::new(&mut duration);
+			let drag_value = drag_value.range(0..=2000).speed(10);
+			if ui.add(drag_value).changed() {
+				animations.duration = Some(duration);
+			}
+
+			let mut default = animations.duration.is_none();
+			if ui.checkbox(&mut default, "Default").changed() {
+				animations.duration = (!default).then_some(DEFAULT_ANIMATION_DURATION);
+			}
+		});
+	}
+
+	fn global_easing_ui(&mut self, ui: &mut egui::Ui) {
+		ui.label("Easing");
+
+		let selected = &mut self.config.animations.easing;
+		egui::ComboBox::new("global_easing", "")
+			.selected_text(format!("{}", selected))
+			.show_ui(ui, |ui| {
+				for option in Easing::ALL {
+					ui.selectable_value(selected, option, format!("{}", option));
+				}
+			});
+	}
+
+	fn global_theme_ui(&mut self, ui: &mut egui::Ui) {
+		ui.label("Theme");
+
+		let selected = &mut self.config.theme;
+		let selected_text = selected.as_deref().unwrap_or(DEFAULT_THEME);
+
+		egui::ComboBox::new("global_theme", "")
+			.selected_text(selected_text)
+			.show_ui(ui, |ui| {
+				for theme in &self.themes {
+					let value = (theme != DEFAULT_THEME).then(|| theme.clone());
+					ui.selectable_value(selected, value, theme);
+				}
+			});
+	}
+
+	fn global_renderer_ui(&mut self, ui: &mut egui::Ui) {
+		ui.label("Renderer").on_hover_text("Requires a restart");
+
+		let selected = &mut self.config.renderer;
+		egui::ComboBox::new("global_renderer", "")
+			.selected_text(format!("{}", selected))
+			.show_ui(ui, |ui| {
+				for option in Renderer::ALL {
+					ui.selectable_value(selected, option, format!("{}", option));
+				}
+			});
+	}
+
+	fn global_komorebi_start_command_ui(&mut self, ui: &mut egui::Ui) {
+		ui.label("komorebi start command");
+
+		let mut command = self
+			.config
+			.komorebi_start_command
+			.clone()
+			.unwrap_or_default();
+		let text_edit =
+			egui::TextEdit::singleline(&mut command).hint_text(DEFAULT_KOMOREBI_START_COMMAND);
+		if ui.add(text_edit).changed() {
+			self.config.komorebi_start_command = (!command.is_empty()).then_some(command);
+		}
+	}
+
+	fn global_log_level_ui(&mut self, ui: &mut egui::Ui) {
+		ui.label("Log level");
+
+		let level = &mut self.config.logging.level;
+		let text_edit = egui::TextEdit::singleline(level).hint_text(LOG_LEVEL_HINT);
+		ui.add(text_edit);
+	}
+
+	fn global_log_format_ui(&mut self, ui: &mut egui::Ui) {
+		ui.label("Log format").on_hover_text("Requires a restart");
+
+		let selected = &mut self.config.logging.format;
+		egui::ComboBox::new("global_log_format", "")
+			.selected_text(format!("{}", selected))
+			.show_ui(ui, |ui| {
+				for option in LogFormat::ALL {
+					ui.selectable_value(selected, option, format!("{}", option));
+				}
+			});
+	}
+
 	fn global_active_indicator_color_ui(&mut self, ui: &mut egui::Ui) {
 		ui.label("Active Indicator");
 
@@ -141,6 +337,48 @@ impl SettingsWindowView {
 		}
 	}
 
+	fn global_unfocused_active_indicator_color_ui(&mut self, ui: &mut egui::Ui) {
+		ui.label("Unfocused Active Indicator");
+
+		let colors = &self.config.colors;
+		let mut color = colors
+			.unfocused_active_indicator
+			.clone()
+			.unwrap_or_default();
+
+		let text_edit = egui::TextEdit::singleline(&mut color);
+		let text_edit = text_edit.hint_text("#FFFFFFFF");
+
+		if ui.add(text_edit).changed() {
+			self.config.colors.unfocused_active_indicator = (!color.is_empty()).then_some(color);
+		}
+	}
+
+	fn global_unfocused_opacity_ui(&mut self, ui: &mut egui::Ui) {
+		ui.label("Unfocused Opacity");
+
+		let mut opacity = self.config.colors.unfocused_opacity.unwrap_or(1.0);
+		let drag_value = egui::DragValue::new(&mut opacity);
+		let drag_value = drag_value.range(0.0..=1.0).speed(0.01);
+		if ui.add(drag_value).changed() {
+			self.config.colors.unfocused_opacity = Some(opacity);
+		}
+	}
+
+	fn global_focus_bar_color_ui(&mut self, ui: &mut egui::Ui) {
+		ui.label("Focus Bar");
+
+		let colors = &self.config.colors;
+		let mut color = colors.focus_bar.clone().unwrap_or_default();
+
+		let text_edit = egui::TextEdit::singleline(&mut color);
+		let text_edit = text_edit.hint_text("#FFFFFFFF");
+
+		if ui.add(text_edit).changed() {
+			self.config.colors.focus_bar = (!color.is_empty()).then_some(color);
+		}
+	}
+
 	fn global_settings_ui(&mut self, ui: &mut egui::Ui) {
 		ui.heading("Global Settings");
 
@@ -161,25 +399,113 @@ impl SettingsWindowView {
 				self.global_font_weight_ui(ui);
 				ui.end_row();
 
+				self.global_font_size_ui(ui);
+				ui.end_row();
+
+				self.global_label_format_ui(ui);
+				ui.end_row();
+
+				self.global_indicator_style_ui(ui);
+				ui.end_row();
+
+				self.global_sliding_indicator_ui(ui);
+				ui.end_row();
+
+				self.global_reduce_motion_ui(ui);
+				ui.end_row();
+
+				self.global_animation_duration_ui(ui);
+				ui.end_row();
+
+				self.global_easing_ui(ui);
+				ui.end_row();
+
+				self.global_theme_ui(ui);
+				ui.end_row();
+
+				self.global_sync_komorebi_theme_ui(ui);
+				ui.end_row();
+
 				self.global_active_indicator_color_ui(ui);
 				ui.end_row();
 
 				self.global_busy_indicator_color_ui(ui);
 				ui.end_row();
+
+				self.global_unfocused_active_indicator_color_ui(ui);
+				ui.end_row();
+
+				self.global_unfocused_opacity_ui(ui);
+				ui.end_row();
+
+				self.global_focus_bar_color_ui(ui);
+				ui.end_row();
+
+				self.global_renderer_ui(ui);
+				ui.end_row();
+
+				self.global_komorebi_start_command_ui(ui);
+				ui.end_row();
+
+				self.global_log_level_ui(ui);
+				ui.end_row();
+
+				self.global_log_format_ui(ui);
+				ui.end_row();
+			});
+	}
+
+	fn floating_ui(&mut self, ui: &mut egui::Ui, monitor_id: &str) {
+		let monitor_config = self.config.get_monitor_mut(monitor_id);
+
+		ui.label("Floating");
+		ui.checkbox(&mut monitor_config.floating, "Show outside the taskbar");
+	}
+
+	fn keep_visible_ui(&mut self, ui: &mut egui::Ui, monitor_id: &str) {
+		let monitor_config = self.config.get_monitor_mut(monitor_id);
+
+		ui.label("Keep visible");
+		ui.checkbox(
+			&mut monitor_config.keep_visible,
+			"Show while the auto-hidden taskbar is hidden",
+		);
+	}
+
+	fn anchor_ui(&mut self, ui: &mut egui::Ui, monitor_id: &str) {
+		let monitor_config = self.config.get_monitor_mut(monitor_id);
+
+		ui.label("Anchor");
+
+		let selected = &mut monitor_config.anchor;
+		egui::ComboBox::new("anchor", "")
+			.selected_text(format!("{}", selected))
+			.show_ui(ui, |ui| {
+				for option in Anchor::ALL {
+					ui.selectable_value(selected, option, format!("{}", option));
+				}
 			});
 	}
 
 	fn x_ui(&mut self, ui: &mut egui::Ui, monitor_id: &str) {
 		let monitor_config = self.config.get_monitor_mut(monitor_id);
 
-		ui.label("X");
+		let label = match monitor_config.anchor {
+			Anchor::Absolute => "X",
+			_ => "X Offset",
+		};
+		ui.label(label);
 		ui.add(egui::DragValue::new(&mut monitor_config.x));
 	}
 
 	fn y_ui(&mut self, ui: &mut egui::Ui, monitor_id: &str) {
 		let monitor_config = self.config.get_monitor_mut(monitor_id);
 
-		ui.label("Y");
+		let label = match monitor_config.anchor {
+			Anchor::Absolute => "Y",
+			_ => "Y Offset",
+		};
+		ui.label(label);
 		ui.add(egui::DragValue::new(&mut monitor_config.y));
 	}
 
@@ -260,15 +586,24 @@ impl SettingsWindowView {
 		ui.label("Font Family");
 		ui.horizontal(|ui| {
 			let mut inherit = monitor_config.font_family.is_none();
-			let mut font_family = monitor_config.font_family.clone().unwrap_or_default();
+			let key = Some(monitor_id.to_string());
+			let font_family = self.font_family_texts.entry(key).or_insert_with(|| {
+				let font_family = monitor_config.font_family.as_ref();
+				font_family.map(ToString::to_string).unwrap_or_default()
+			});
 
-			let text_edit = egui::TextEdit::singleline(&mut font_family).hint_text("i.e Roboto");
+			let text_edit = egui::TextEdit::singleline(font_family).hint_text(FONT_FAMILY_HINT);
 			if ui.add_enabled(!inherit, text_edit).changed() {
-				monitor_config.font_family = (!font_family.is_empty()).then_some(font_family);
+				monitor_config.font_family = FontFamily::from_text(font_family);
 			}
 
 			if ui.checkbox(&mut inherit, "Inherit").changed() {
-				monitor_config.font_family = if inherit { None } else { Some(String::new()) };
+				font_family.clear();
+				monitor_config.font_family = if inherit {
+					None
+				} else {
+					Some(FontFamily::Single(String::new()))
+				};
 			}
 		});
 	}
@@ -292,6 +627,69 @@ impl SettingsWindowView {
 		});
 	}
 
+	fn font_size_ui(&mut self, ui: &mut egui::Ui, monitor_id: &str) {
+		let monitor_config = self.config.get_monitor_mut(monitor_id);
+		ui.label("Font Size");
+		ui.horizontal(|ui| {
+			let mut inherit = monitor_config.font_size.is_none();
+			let mut font_size = monitor_config.font_size.unwrap_or(DEFAULT_FONT_SIZE);
+
+			let drag_value = egui::DragValue::new(&mut font_size);
+			let drag_value = drag_value.range(FONT_SIZE_RANGE).speed(0.5);
+			if ui.add_enabled(!inherit, drag_value).changed() {
+				monitor_config.font_size = Some(font_size);
+			}
+
+			if ui.checkbox(&mut inherit, "Inherit").changed() {
+				monitor_config.font_size = if inherit {
+					None
+				} else {
+					Some(DEFAULT_FONT_SIZE)
+				};
+			}
+		});
+	}
+
+	fn label_format_ui(&mut self, ui: &mut egui::Ui, monitor_id: &str) {
+		let monitor_config = self.config.get_monitor_mut(monitor_id);
+		ui.label("Label Format");
+		ui.horizontal(|ui| {
+			let mut inherit = monitor_config.label_format.is_none();
+			let mut label_format = monitor_config.label_format.clone().unwrap_or_default();
+
+			let text_edit =
+				egui::TextEdit::singleline(&mut label_format).hint_text("i.e {index}: {name}");
+			if ui.add_enabled(!inherit, text_edit).changed() {
+				monitor_config.label_format = (!label_format.is_empty()).then_some(label_format);
+			}
+
+			if ui.checkbox(&mut inherit, "Inherit").changed() {
+				monitor_config.label_format = if inherit { None } else { Some(String::new()) };
+			}
+		});
+	}
+
+	fn indicator_style_ui(&mut self, ui: &mut egui::Ui, monitor_id: &str) {
+		let monitor_config = self.config.get_monitor_mut(monitor_id);
+
+		ui.label("Indicator Style");
+
+		let selected = &mut monitor_config.indicator_style;
+		let selected_text = match selected {
+			Some(style) => format!("{}", style),
+			None => "Inherit".to_string(),
+		};
+
+		egui::ComboBox::new("indicator_style", "")
+			.selected_text(selected_text)
+			.show_ui(ui, |ui| {
+				ui.selectable_value(selected, None, "Inherit");
+				for option in IndicatorStyle::ALL {
+					ui.selectable_value(selected, Some(option), format!("{}", option));
+				}
+			});
+	}
+
 	fn active_indicator_color_ui(&mut self, ui: &mut egui::Ui, monitor_id: &str) {
 		let monitor_config = self.config.get_monitor_mut(monitor_id);
 		ui.label("Active Indicator");
@@ -336,10 +734,87 @@ impl SettingsWindowView {
 		});
 	}
 
+	fn unfocused_active_indicator_color_ui(&mut self, ui: &mut egui::Ui, monitor_id: &str) {
+		let monitor_config = self.config.get_monitor_mut(monitor_id);
+		ui.label("Unfocused Active Indicator");
+		ui.horizontal(|ui| {
+			let colors = &monitor_config.colors;
+
+			let mut inherit = colors.unfocused_active_indicator.is_none();
+			let mut color = colors
+				.unfocused_active_indicator
+				.clone()
+				.unwrap_or_default();
+
+			let text_edit = egui::TextEdit::singleline(&mut color);
+			let text_edit = text_edit.hint_text("#FFFFFFFF");
+			if ui.add_enabled(!inherit, text_edit).changed() {
+				monitor_config.colors.unfocused_active_indicator =
+					(!color.is_empty()).then_some(color);
+			}
+
+			if ui.checkbox(&mut inherit, "Inherit").changed() {
+				monitor_config.colors.unfocused_active_indicator =
+					if inherit { None } else { Some(String::new()) };
+			}
+		});
+	}
+
+	fn unfocused_opacity_ui(&mut self, ui: &mut egui::Ui, monitor_id: &str) {
+		let monitor_config = self.config.get_monitor_mut(monitor_id);
+		ui.label("Unfocused Opacity");
+		ui.horizontal(|ui| {
+			let mut inherit = monitor_config.colors.unfocused_opacity.is_none();
+			let mut opacity = monitor_config.colors.unfocused_opacity.unwrap_or(1.0);
+
+			let drag_value = egui::DragValue::new(&mut opacity);
+			let drag_value = drag_value.range(0.0..=1.0).speed(0.01);
+			if ui.add_enabled(!inherit, drag_value).changed() {
+				monitor_config.colors.unfocused_opacity = Some(opacity);
+			}
+
+			if ui.checkbox(&mut inherit, "Inherit").changed() {
+				monitor_config.colors.unfocused_opacity = if inherit { None } else { Some(1.0) };
+			}
+		});
+	}
+
+	fn focus_bar_color_ui(&mut self, ui: &mut egui::Ui, monitor_id: &str) {
+		let monitor_config = self.config.get_monitor_mut(monitor_id);
+		ui.label("Focus Bar");
+		ui.horizontal(|ui| {
+			let colors = &monitor_config.colors;
+
+			let mut inherit = colors.focus_bar.is_none();
+			let mut color = colors.focus_bar.clone().unwrap_or_default();
+
+			let text_edit = egui::TextEdit::singleline(&mut color);
+			let text_edit = text_edit.hint_text("#FFFFFFFF");
+			if ui.add_enabled(!inherit, text_edit).changed() {
+				monitor_config.colors.focus_bar = (!color.is_empty()).then_some(color);
+			}
+
+			if ui.checkbox(&mut inherit, "Inherit").changed() {
+				monitor_config.colors.focus_bar = if inherit { None } else { Some(String::new()) };
+			}
+		});
+	}
+
 	fn monitor_settings_ui(&mut self, ui: &mut egui::Ui, monitor_id: &str) {
 		// Set text edit width to 40% of available width for better UX
 		ui.style_mut().spacing.text_edit_width = ui.available_width() * 0.4;
 
+		self.floating_ui(ui, monitor_id);
+		ui.end_row();
+
+		if !self.config.get_monitor(monitor_id).floating {
+			self.keep_visible_ui(ui, monitor_id);
+			ui.end_row();
+
+			self.anchor_ui(ui, monitor_id);
+			ui.end_row();
+		}
+
 		self.x_ui(ui, monitor_id);
 		ui.end_row();
 
@@ -364,11 +839,29 @@ impl SettingsWindowView {
 		self.font_weight_ui(ui, monitor_id);
 		ui.end_row();
 
+		self.font_size_ui(ui, monitor_id);
+		ui.end_row();
+
+		self.label_format_ui(ui, monitor_id);
+		ui.end_row();
+
+		self.indicator_style_ui(ui, monitor_id);
+		ui.end_row();
+
 		self.active_indicator_color_ui(ui, monitor_id);
 		ui.end_row();
 
 		self.busy_indicator_color_ui(ui, monitor_id);
 		ui.end_row();
+
+		self.unfocused_active_indicator_color_ui(ui, monitor_id);
+		ui.end_row();
+
+		self.unfocused_opacity_ui(ui, monitor_id);
+		ui.end_row();
+
+		self.focus_bar_color_ui(ui, monitor_id);
+		ui.end_row();
 	}
 
 	fn actions_ui(&mut self, ui: &mut egui::Ui) {
@@ -425,9 +918,6 @@ impl EguiView for SettingsWindowView {
 	}
 
 	fn update(&mut self, ctx: &egui::Context) {
-		// Preview config on each update
-		self.preview_config();
-
 		egui::TopBottomPanel::bottom("actions").show(ctx, |ui| {
 			ui.add_space(4.0);
 			self.actions_ui(ui);
@@ -437,6 +927,9 @@ impl EguiView for SettingsWindowView {
 		egui::CentralPanel::default().show(ctx, |ui| {
 			egui::ScrollArea::vertical().show(ui, |ui| self.ui(ui));
 		});
+
+		// Preview config whenever it changes
+		self.preview_config();
 	}
 }
 
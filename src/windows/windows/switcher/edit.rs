@@ -0,0 +1,366 @@
+use std::time::Duration;
+
+use anyhow::Context;
+use windows::Win32::Foundation::*;
+use windows::Win32::UI::HiDpi::GetDpiForWindow;
+use windows::Win32::UI::Input::KeyboardAndMouse::{
+	GetAsyncKeyState, VIRTUAL_KEY, VK_ESCAPE, VK_RETURN,
+};
+use windows::Win32::UI::WindowsAndMessaging::*;
+
+use super::SwitcherWindowView;
+use crate::config::{Anchor, MonitorConfig};
+use crate::windows::utils;
+
+/// Distance within which the switcher snaps to the taskbar edges, center
+/// and elements, or the monitor edges and center for floating switchers,
+/// while dragging, in logical pixels.
+const SNAP_DISTANCE: f32 = 8.0;
+
+/// Size of the resize grip, in points.
+const GRIP_SIZE: f32 = 8.0;
+
+/// Minimum size of the switcher when resizing, in physical pixels.
+const MIN_SIZE: i32 = 16;
+
+/// The host is never activated so it doesn't receive keyboard input,
+/// instead the keyboard is polled at this interval while editing.
+const KEY_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Bounds of the host relative to the taskbar client area, or in screen
+/// coordinates for floating switchers, in physical pixels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Bounds {
+	x: i32,
+	y: i32,
+	width: i32,
+	height: i32,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum DragKind {
+	Move,
+	Resize,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Drag {
+	kind: DragKind,
+	/// Cursor position when the drag started, in screen coordinates.
+	cursor: POINT,
+	/// Bounds when the drag started.
+	bounds: Bounds,
+}
+
+/// State of the interactive edit position mode.
+#[derive(Debug)]
+pub struct EditState {
+	bounds: Bounds,
+	/// Bounds when entering edit mode, restored on cancel.
+	original: Bounds,
+	drag: Option<Drag>,
+	/// Foreground window when the host was last clicked, the keyboard is
+	/// polled globally so keys are only handled until another window is
+	/// activated, or while the cursor is over the host.
+	clicked_foreground: Option<HWND>,
+}
+
+impl EditState {
+	fn new(host: HWND, taskbar: Option<HWND>) -> anyhow::Result<Self> {
+		let rect = utils::rect_in_parent(taskbar, host).context("Failed to get host bounds")?;
+		let bounds = Bounds {
+			x: rect.left,
+			y: rect.top,
+			width: rect.right - rect.left,
+			height: rect.bottom - rect.top,
+		};
+
+		Ok(Self {
+			bounds,
+			original: bounds,
+			drag: None,
+			clicked_foreground: None,
+		})
+	}
+}
+
+impl SwitcherWindowView {
+	/// Enters the edit position mode.
+	pub(super) fn start_edit_position(&mut self) -> anyhow::Result<()> {
+		if self.floating_while_hidden {
+			anyhow::bail!("Can't edit position while the taskbar is hidden");
+		}
+
+		let taskbar = self.taskbar.map(|t| t.hwnd);
+		self.edit = Some(EditState::new(self.host, taskbar)?);
+		Ok(())
+	}
+
+	/// Draws the edit position overlay, moves or resizes the host while
+	/// dragging, and saves or reverts the result when done.
+	pub(super) fn edit_position_ui(
+		&mut self,
+		ctx: &egui::Context,
+		monitor_config: &MonitorConfig,
+	) -> anyhow::Result<()> {
+		let Some(mut edit) = self.edit.take() else {
+			return Ok(());
+		};
+
+		ctx.request_repaint_after(KEY_POLL_INTERVAL);
+
+		if ctx.input(|i| i.pointer.any_pressed()) {
+			edit.clicked_foreground = Some(unsafe { GetForegroundWindow() });
+		}
+
+		// Ignore keys pressed in other apps
+		let foreground = unsafe { GetForegroundWindow() };
+		let keys_enabled = is_cursor_over(self.host) || edit.clicked_foreground == Some(foreground);
+
+		if keys_enabled && is_key_down(VK_ESCAPE) {
+			tracing::info!("Reverting switcher position");
+			self.set_host_bounds(edit.original)?;
+			self.prev_bounds = None;
+			return Ok(());
+		}
+
+		let mut confirm = keys_enabled && is_key_down(VK_RETURN);
+
+		let resizable = !monitor_config.auto_width || !monitor_config.auto_height;
+		let color = self.accent_color().unwrap_or(egui::Color32::WHITE);
+		let screen_rect = ctx.screen_rect();
+
+		egui::Area::new(egui::Id::new("edit_position"))
+			.order(egui::Order::Foreground)
+			.fixed_pos(screen_rect.min)
+			.show(ctx, |ui| {
+				let stroke = egui::Stroke::new(2.0, color);
+				ui.painter()
+					.rect_stroke(screen_rect, 4.0, stroke, egui::StrokeKind::Inside);
+
+				let response = ui
+					.interact(
+						screen_rect,
+						ui.id().with("move"),
+						egui::Sense::click_and_drag(),
+					)
+					.on_hover_cursor(egui::CursorIcon::Grab)
+					.on_hover_text("Drag to move, Enter or double-click to save, Escape to cancel");
+
+				if response.double_clicked() {
+					confirm = true;
+				}
+
+				if response.drag_started() {
+					edit.drag = Some(Drag {
+						kind: DragKind::Move,
+						cursor: cursor_pos(),
+						bounds: edit.bounds,
+					});
+				}
+
+				// Resize grip in the bottom right corner, added last to take
+				// precedence over the move area.
+				if resizable {
+					let size = egui::vec2(GRIP_SIZE, GRIP_SIZE);
+					let grip = egui::Rect::from_min_size(screen_rect.max - size, size);
+					ui.painter().rect_filled(grip, 1.0, color);
+
+					let response = ui
+						.interact(grip, ui.id().with("resize"), egui::Sense::drag())
+						.on_hover_cursor(egui::CursorIcon::ResizeNwSe);
+
+					if response.drag_started() {
+						edit.drag = Some(Drag {
+							kind: DragKind::Resize,
+							cursor: cursor_pos(),
+							bounds: edit.bounds,
+						});
+					}
+				}
+			});
+
+		if !ctx.input(|i| i.pointer.primary_down()) {
+			edit.drag = None;
+		}
+
+		if let Some(drag) = edit.drag {
+			let cursor = cursor_pos();
+			let (dx, dy) = (cursor.x - drag.cursor.x, cursor.y - drag.cursor.y);
+
+			let bounds = match drag.kind {
+				DragKind::Move => self.moved(drag.bounds, dx, dy)?,
+				DragKind::Resize => resized(monitor_config, drag.bounds, dx, dy),
+			};
+
+			if bounds != edit.bounds {
+				self.set_host_bounds(bounds)?;
+				edit.bounds = bounds;
+			}
+		}
+
+		if confirm {
+			self.save_edit_position(&edit)?;
+			return Ok(());
+		}
+
+		self.edit = Some(edit);
+
+		Ok(())
+	}
+
+	/// Saves the edited position and size to the monitor config.
+	fn save_edit_position(&mut self, edit: &EditState) -> anyhow::Result<()> {
+		tracing::info!("Saving switcher position: {:?}", edit.bounds);
+
+		let Bounds {
+			x,
+			y,
+			width,
+			height,
+		} = edit.bounds;
+
+		let mut config = self.config.write().unwrap();
+		let monitor_config = config.get_monitor_mut(&self.monitor_state.id);
+
+		let (x, y) = match self.taskbar_layout {
+			Some(layout) => layout.host_offset(monitor_config, (x, y), width, height),
+			None => {
+				let rect = &self.monitor_state.rect;
+				(x - rect.left, y - rect.top)
+			}
+		};
+		monitor_config.x = x;
+		monitor_config.y = y;
+
+		if !monitor_config.auto_width {
+			monitor_config.width = width;
+		}
+		if !monitor_config.auto_height {
+			monitor_config.height = height;
+		}
+
+		config.save()?;
+
+		// Force repositioning the host from the saved config
+		self.prev_bounds = None;
+
+		Ok(())
+	}
+
+	/// Moves the bounds by the given delta, snapping to the anchors along the
+	/// main axis of the taskbar and keeping them inside the taskbar, or to the
+	/// edges and center of the monitor for floating switchers.
+	fn moved(&self, bounds: Bounds, dx: i32, dy: i32) -> anyhow::Result<Bounds> {
+		let Bounds { width, height, .. } = bounds;
+
+		let (area, scale, x_targets, y_targets) = match self.taskbar_layout {
+			Some(layout) => {
+				let anchors = Anchor::ALL
+					.into_iter()
+					.filter(|anchor| *anchor != Anchor::Absolute)
+					.map(|anchor| layout.anchor_position(anchor, width, height))
+					.collect();
+				let (x_targets, y_targets) = if layout.edge.is_vertical() {
+					(Vec::new(), anchors)
+				} else {
+					(anchors, Vec::new())
+				};
+				(layout.client, layout.scale_factor(), x_targets, y_targets)
+			}
+			None => {
+				let rect = &self.monitor_state.rect;
+				let area = RECT {
+					left: rect.left,
+					top: rect.top,
+					right: rect.right,
+					bottom: rect.bottom,
+				};
+				let dpi = unsafe { GetDpiForWindow(self.host) };
+				let scale = dpi as f32 / USER_DEFAULT_SCREEN_DPI as f32;
+				let x_targets = vec![
+					rect.left,
+					(rect.left + rect.right - width) / 2,
+					rect.right - width,
+				];
+				let y_targets = vec![
+					rect.top,
+					(rect.top + rect.bottom - height) / 2,
+					rect.bottom - height,
+				];
+				(area, scale, x_targets, y_targets)
+			}
+		};
+
+		let distance = (SNAP_DISTANCE * scale).round() as i32;
+		let x = snap(bounds.x + dx, &x_targets, distance);
+		let y = snap(bounds.y + dy, &y_targets, distance);
+
+		let x = x.clamp(area.left, (area.right - width).max(area.left));
+		let y = y.clamp(area.top, (area.bottom - height).max(area.top));
+
+		Ok(Bounds { x, y, ..bounds })
+	}
+
+	fn set_host_bounds(&self, bounds: Bounds) -> anyhow::Result<()> {
+		unsafe {
+			SetWindowPos(
+				self.host,
+				None,
+				bounds.x,
+				bounds.y,
+				bounds.width,
+				bounds.height,
+				SWP_NOZORDER | SWP_NOACTIVATE | SWP_FRAMECHANGED,
+			)
+		}?;
+
+		Ok(())
+	}
+}
+
+/// Resizes the bounds by the given delta, only along the axes that are not
+/// auto-sized.
+fn resized(monitor_config: &MonitorConfig, bounds: Bounds, dx: i32, dy: i32) -> Bounds {
+	let mut bounds = bounds;
+
+	if !monitor_config.auto_width {
+		bounds.width = (bounds.width + dx).max(MIN_SIZE);
+	}
+	if !monitor_config.auto_height {
+		bounds.height = (bounds.height + dy).max(MIN_SIZE);
+	}
+
+	bounds
+}
+
+/// Snaps the position to the closest target within the distance, if any.
+fn snap(position: i32, targets: &[i32], distance: i32) -> i32 {
+	targets
+		.iter()
+		.copied()
+		.filter(|target| (target - position).abs() <= distance)
+		.min_by_key(|target| (target - position).abs())
+		.unwrap_or(position)
+}
+
+fn cursor_pos() -> POINT {
+	let mut point = POINT::default();
+	let _ = unsafe { GetCursorPos(&mut point) };
+	point
+}
+
+fn is_cursor_over(hwnd: HWND) -> bool {
+	let mut rect = RECT::default();
+	if unsafe { GetWindowRect(hwnd, &mut rect) }.is_err() {
+		return false;
+	}
+
+	let cursor = cursor_pos();
+	(rect.left..rect.right).contains(&cursor.x) && (rect.top..rect.bottom).contains(&cursor.y)
+}
+
+fn is_key_down(key: VIRTUAL_KEY) -> bool {
+	let state = unsafe { GetAsyncKeyState(key.0 as i32) };
+	(state as u16 & 0x8000) != 0
+}
@@ -6,6 +6,7 @@ use winit::event_loop::EventLoopProxy;
 
 use crate::config::MonitorConfig;
 use crate::windows::app::AppMessage;
+use crate::windows::taskbar::{self, TaskbarLayout};
 use crate::windows::utils;
 
 #[cfg(debug_assertions)]
@@ -13,15 +14,25 @@ const HOST_CLASSNAME: PCWSTR = w!("komorebi-switcher-debug::host");
 #[cfg(not(debug_assertions))]
 const HOST_CLASSNAME: PCWSTR = w!("komorebi-switcher::host");
 
-pub unsafe fn create_host(
-	taskbar_hwnd: HWND,
-	proxy: EventLoopProxy<AppMessage>,
-	monitor_config: &MonitorConfig,
-) -> anyhow::Result<HWND> {
-	let hinstance = unsafe { GetModuleHandleW(None) }?;
+/// Timer used to watch the taskbar layout for changes.
+const TASKBAR_LAYOUT_TIMER_ID: usize = 1;
+/// Interval of [`TASKBAR_LAYOUT_TIMER_ID`], in milliseconds.
+const TASKBAR_LAYOUT_TIMER_INTERVAL: u32 = 1000;
+/// Interval of [`TASKBAR_LAYOUT_TIMER_ID`] while taskbars are set to auto-hide,
+/// short enough to follow them showing and hiding.
+const TASKBAR_LAYOUT_AUTO_HIDE_TIMER_INTERVAL: u32 = 250;
 
-	let mut rect = RECT::default();
-	GetClientRect(taskbar_hwnd, &mut rect)?;
+const HOST_STYLE: WINDOW_STYLE = WINDOW_STYLE(WS_CHILD.0 | WS_VISIBLE.0 | WS_CLIPSIBLINGS.0);
+const HOST_EX_STYLE: WINDOW_EX_STYLE =
+	WINDOW_EX_STYLE(WS_EX_NOACTIVATE.0 | WS_EX_NOREDIRECTIONBITMAP.0);
+const FLOATING_HOST_STYLE: WINDOW_STYLE =
+	WINDOW_STYLE(WS_POPUP.0 | WS_VISIBLE.0 | WS_CLIPSIBLINGS.0);
+const FLOATING_HOST_EX_STYLE: WINDOW_EX_STYLE = WINDOW_EX_STYLE(
+	WS_EX_NOACTIVATE.0 | WS_EX_NOREDIRECTIONBITMAP.0 | WS_EX_TOPMOST.0 | WS_EX_TOOLWINDOW.0,
+);
+
+unsafe fn register_host_class() -> anyhow::Result<()> {
+	let hinstance = unsafe { GetModuleHandleW(None) }?;
 
 	let wc = WNDCLASSW {
 		hInstance: hinstance.into(),
@@ -33,22 +44,58 @@ pub unsafe fn create_host(
 
 	RegisterClassW(&wc);
 
-	let userdata = WndProcUserData { proxy };
+	Ok(())
+}
 
-	let height = if monitor_config.auto_height {
-		rect.bottom - rect.top
+pub unsafe fn create_host(
+	taskbar_hwnd: HWND,
+	proxy: EventLoopProxy<AppMessage>,
+	monitor_config: &MonitorConfig,
+) -> anyhow::Result<HWND> {
+	register_host_class()?;
+
+	let mut rect = RECT::default();
+	GetClientRect(taskbar_hwnd, &mut rect)?;
+
+	let taskbar_layout = TaskbarLayout::new(taskbar_hwnd)?;
+
+	// Auto size matches the taskbar thickness, which is its width
+	// for vertical taskbars and its height otherwise.
+	let (width, height) = if taskbar_layout.edge.is_vertical() {
+		let width = if monitor_config.auto_width {
+			rect.right - rect.left
+		} else {
+			monitor_config.width
+		};
+		(width, monitor_config.height)
 	} else {
-		monitor_config.height
+		let height = if monitor_config.auto_height {
+			rect.bottom - rect.top
+		} else {
+			monitor_config.height
+		};
+		(monitor_config.width, height)
+	};
+
+	let (x, y) = taskbar_layout.host_position(monitor_config, width, height);
+
+	let auto_hide = taskbar::is_auto_hide();
+
+	let userdata = WndProcUserData {
+		proxy,
+		taskbar: Some(taskbar_hwnd),
+		taskbar_layout,
+		auto_hide,
 	};
 
 	let hwnd = CreateWindowExW(
-		WS_EX_NOACTIVATE | WS_EX_NOREDIRECTIONBITMAP,
+		HOST_EX_STYLE,
 		HOST_CLASSNAME,
 		PCWSTR::null(),
-		WS_CHILD | WS_VISIBLE | WS_CLIPSIBLINGS,
-		monitor_config.x,
-		monitor_config.y,
-		monitor_config.width,
+		HOST_STYLE,
+		x,
+		y,
+		width,
 		height,
 		Some(taskbar_hwnd),
 		None,
@@ -66,11 +113,103 @@ pub unsafe fn create_host(
 		SWP_NOMOVE | SWP_NOSIZE | SWP_NOACTIVATE,
 	)?;
 
+	// The taskbar doesn't notify its children when its elements move,
+	// i.e when pinned icons or tray icons change, so poll its layout instead.
+	set_taskbar_layout_timer(hwnd, auto_hide);
+
+	Ok(hwnd)
+}
+
+/// Creates a host as a borderless, always on top and non-activating popup,
+/// positioned relative to the top left corner of the monitor.
+///
+/// Used when the monitor has no taskbar, or the switcher is set to float.
+pub unsafe fn create_floating_host(
+	monitor_rect: &crate::komorebi::Rect,
+	proxy: EventLoopProxy<AppMessage>,
+	monitor_config: &MonitorConfig,
+) -> anyhow::Result<HWND> {
+	register_host_class()?;
+
+	let userdata = WndProcUserData {
+		proxy,
+		taskbar: None,
+		taskbar_layout: TaskbarLayout::default(),
+		auto_hide: false,
+	};
+
+	let hwnd = CreateWindowExW(
+		FLOATING_HOST_EX_STYLE,
+		HOST_CLASSNAME,
+		PCWSTR::null(),
+		FLOATING_HOST_STYLE,
+		monitor_rect.left + monitor_config.x,
+		monitor_rect.top + monitor_config.y,
+		monitor_config.width,
+		monitor_config.height,
+		None,
+		None,
+		None,
+		Some(Box::into_raw(Box::new(userdata)) as _),
+	)?;
+
 	Ok(hwnd)
 }
 
+/// Moves a taskbar host out of the taskbar into a floating popup, or back
+/// into the taskbar.
+///
+/// Used to keep the switcher visible while an auto-hidden taskbar is hidden.
+pub unsafe fn set_floating(hwnd: HWND, taskbar_hwnd: HWND, floating: bool) -> anyhow::Result<()> {
+	let (style, ex_style, parent, insert_after) = if floating {
+		(
+			FLOATING_HOST_STYLE,
+			FLOATING_HOST_EX_STYLE,
+			None,
+			HWND_TOPMOST,
+		)
+	} else {
+		(HOST_STYLE, HOST_EX_STYLE, Some(taskbar_hwnd), HWND_TOP)
+	};
+
+	// Styles must be updated before changing the parent
+	SetWindowLongPtrW(hwnd, GWL_STYLE, style.0 as _);
+	SetWindowLongPtrW(hwnd, GWL_EXSTYLE, ex_style.0 as _);
+	SetParent(hwnd, parent)?;
+
+	SetWindowPos(
+		hwnd,
+		Some(insert_after),
+		0,
+		0,
+		0,
+		0,
+		SWP_NOMOVE | SWP_NOSIZE | SWP_NOACTIVATE | SWP_FRAMECHANGED,
+	)?;
+
+	Ok(())
+}
+
+/// Starts or restarts the timer polling the taskbar layout, polling faster
+/// while taskbars are set to auto-hide.
+unsafe fn set_taskbar_layout_timer(hwnd: HWND, auto_hide: bool) {
+	let interval = if auto_hide {
+		TASKBAR_LAYOUT_AUTO_HIDE_TIMER_INTERVAL
+	} else {
+		TASKBAR_LAYOUT_TIMER_INTERVAL
+	};
+
+	SetTimer(Some(hwnd), TASKBAR_LAYOUT_TIMER_ID, interval, None);
+}
+
 struct WndProcUserData {
 	proxy: EventLoopProxy<AppMessage>,
+	/// The taskbar hosting the switcher, kept as the host may be moved out of it.
+	taskbar: Option<HWND>,
+	/// Last known taskbar layout, to detect changes.
+	taskbar_layout: TaskbarLayout,
+	/// Whether taskbars were set to auto-hide on the last layout check.
+	auto_hide: bool,
 }
 
 impl WndProcUserData {
@@ -101,6 +240,31 @@ unsafe extern "system" fn wndproc_host(
 			}
 		}
 
+		// Notify app to reposition anchored switchers if the taskbar layout changed
+		WM_TIMER if wparam.0 == TASKBAR_LAYOUT_TIMER_ID => {
+			let userdata = WndProcUserData::from_hwnd(hwnd);
+
+			let auto_hide = taskbar::is_auto_hide();
+			if auto_hide != userdata.auto_hide {
+				userdata.auto_hide = auto_hide;
+				set_taskbar_layout_timer(hwnd, auto_hide);
+			}
+
+			let layout = userdata.taskbar.map(TaskbarLayout::new);
+			if let Some(Ok(layout)) = layout {
+				if layout != userdata.taskbar_layout {
+					userdata.taskbar_layout = layout;
+					let message = AppMessage::TaskbarLayoutChanged {
+						taskbar: userdata.taskbar.map_or(0, |hwnd| hwnd.0 as isize),
+						layout,
+					};
+					if let Err(e) = userdata.proxy.send_event(message) {
+						tracing::error!("Failed to send `AppMessage::TaskbarLayoutChanged`: {e}")
+					}
+				}
+			}
+		}
+
 		// Resize children when this host is resized
 		WM_SIZE => {
 			let mut rect = RECT::default();
@@ -1,3 +1,4 @@
+use std::cell::Cell;
 use std::num::NonZero;
 use std::sync::{Arc, RwLock};
 
@@ -12,27 +13,40 @@ use winit::event_loop::ActiveEventLoop;
 use winit::platform::windows::WindowAttributesExtWindows;
 use winit::window::WindowAttributes;
 
-use crate::config::Config;
+use crate::color_expr::{self, SystemColors};
+use crate::config::{Anchor, ColorsConfig, Config, MonitorConfig};
 use crate::komorebi::CycleDirection;
+use crate::label::{format_label, DEFAULT_LABEL_FORMAT};
+use crate::theme::ThemeColors;
+use crate::widgets::{
+	egui_color_from_color, ButtonAnimation, ButtonColors, ButtonMetrics, LayoutButton,
+	WorkspaceButton,
+};
 use crate::windows::app::{App, AppMessage};
 use crate::windows::context_menu::AppContextMenu;
-use crate::windows::egui_glue::{EguiView, EguiWindow};
+use crate::windows::egui_glue::{EguiView, EguiWindow, FrameStats};
 use crate::windows::registry;
-use crate::windows::taskbar::Taskbar;
-use crate::windows::utils::egui_color_from_color;
-use crate::windows::widgets::{LayoutButton, WorkspaceButton};
+use crate::windows::taskbar::{Taskbar, TaskbarEdge, TaskbarLayout};
+use crate::windows::utils;
+use crate::windows::windows::debug_overlay::{DebugOverlay, SwitcherDiagnostics};
 
+mod edit;
 mod host;
 
+/// Default spacing between buttons, in points.
+const DEFAULT_SPACING: f32 = 4.0;
+
 impl App {
 	/// Creates the switcher window for the given monitor.
 	///
 	/// The switcher consists of a host window (child of taskbar), and a winit
 	/// window as a child of the host and hosts the egui view.
+	///
+	/// Without a taskbar, the host is a floating window on the monitor instead.
 	pub fn create_switcher_window(
 		&mut self,
 		event_loop: &ActiveEventLoop,
-		taskbar: Taskbar,
+		taskbar: Option<Taskbar>,
 		monitor_state: crate::komorebi::Monitor,
 		context_menu: AppContextMenu,
 	) -> anyhow::Result<EguiWindow> {
@@ -43,7 +57,14 @@ impl App {
 			monitor_config
 		};
 
-		let host = unsafe { host::create_host(taskbar.hwnd, self.proxy.clone(), &monitor_config) }?;
+		let host = match taskbar {
+			Some(taskbar) => unsafe {
+				host::create_host(taskbar.hwnd, self.proxy.clone(), &monitor_config)
+			},
+			None => unsafe {
+				host::create_floating_host(&monitor_state.rect, self.proxy.clone(), &monitor_config)
+			},
+		}?;
 
 		let mut attrs = WindowAttributes::default();
 		attrs = attrs.with_inner_size(PhysicalSize::new(
@@ -67,7 +88,7 @@ impl App {
 			.with_active(false)
 			.with_class_name(class_name)
 			.with_undecorated_shadow(false)
-			.with_no_redirection_bitmap(true)
+			.with_no_redirection_bitmap(self.backend.no_redirection_bitmap())
 			.with_clip_children(false);
 
 		let window = event_loop.create_window(attrs)?;
@@ -77,48 +98,104 @@ impl App {
 			host,
 			taskbar,
 			monitor_state,
+			self.komorebi_connected,
+			self.komorebi_state.theme.clone(),
 			self.config.clone(),
 			context_menu,
+			self.debug_overlay.clone(),
 		)?;
 
-		EguiWindow::new(window, &self.wgpu_instance, state)
+		EguiWindow::new(window, &self.backend, state)
 	}
 }
 
+/// Colors of the switcher resolved against the system colors,
+/// once per frame rather than for every button.
+struct SwitcherColors {
+	text: Option<egui::Color32>,
+	active_indicator: Option<egui::Color32>,
+	busy_indicator: Option<egui::Color32>,
+	unfocused_active_indicator: Option<egui::Color32>,
+	focus_bar: Option<egui::Color32>,
+	buttons: ButtonColors,
+}
+
 pub struct SwitcherWindowView {
 	config: Arc<RwLock<crate::config::Config>>,
 	preview_config: Option<Config>,
 	host: HWND,
-	taskbar: Taskbar,
+	/// The taskbar hosting the switcher, `None` for floating switchers.
+	taskbar: Option<Taskbar>,
+	/// Layout of the taskbar, only refreshed when it changes as inspecting
+	/// the taskbar elements is expensive.
+	taskbar_layout: Option<TaskbarLayout>,
+	/// Whether the user was warned that the `after-start` anchor isn't
+	/// supported by the taskbar.
+	after_start_warned: Cell<bool>,
+	/// Size of the taskbar when it was last shown, as auto-hidden taskbars
+	/// may shrink while hidden.
+	shown_taskbar_size: Cell<Option<(i32, i32)>>,
+	/// Whether the host was moved out of the auto-hidden taskbar while it is
+	/// hidden, see `keep_visible`.
+	floating_while_hidden: bool,
 	context_menu: AppContextMenu,
 	monitor_state: crate::komorebi::Monitor,
+	/// Whether komorebi is running, otherwise `monitor_state` is the last
+	/// known state and is shown greyed out.
+	komorebi_connected: bool,
+	/// Colors mapped from komorebi's theme, used if `sync_komorebi_theme` is enabled.
+	komorebi_theme: Option<ThemeColors>,
 	accent_light2_color: Option<egui::Color32>,
 	accent_color: Option<egui::Color32>,
 	forgreound_color: Option<egui::Color32>,
+	/// System colors referenced by color expressions in the config.
+	system_colors: SystemColors,
+	/// Whether animations are enabled in the OS settings.
+	system_animations_enabled: bool,
 	prev_bounds: Option<egui::Rect>,
-	applied_font: Option<(String, u16)>,
+	applied_font: Option<(Vec<String>, u16)>,
+	/// Set while in the interactive edit position mode.
+	edit: Option<edit::EditState>,
+	debug_overlay: DebugOverlay,
 }
 
 impl SwitcherWindowView {
 	fn new(
 		host: HWND,
-		taskbar: Taskbar,
+		taskbar: Option<Taskbar>,
 		monitor_state: crate::komorebi::Monitor,
+		komorebi_connected: bool,
+		komorebi_theme: Option<ThemeColors>,
 		config: Arc<RwLock<crate::config::Config>>,
 		context_menu: AppContextMenu,
+		debug_overlay: DebugOverlay,
 	) -> anyhow::Result<Self> {
+		let taskbar_layout = taskbar
+			.map(|taskbar| TaskbarLayout::new(taskbar.hwnd))
+			.transpose()?;
+
 		let mut view = Self {
 			host,
 			taskbar,
+			taskbar_layout,
+			after_start_warned: Cell::new(false),
+			shown_taskbar_size: Cell::new(None),
+			floating_while_hidden: false,
 			monitor_state,
+			komorebi_connected,
+			komorebi_theme,
 			context_menu,
 			accent_color: None,
 			accent_light2_color: None,
 			forgreound_color: None,
+			system_colors: SystemColors::default(),
+			system_animations_enabled: true,
 			config,
 			preview_config: None,
 			prev_bounds: None,
 			applied_font: None,
+			edit: None,
+			debug_overlay,
 		};
 
 		// Update system colors initially.
@@ -132,6 +209,39 @@ impl SwitcherWindowView {
 
 /// Getters
 impl SwitcherWindowView {
+	/// Publishes the diagnostics of the switcher to the debug overlay.
+	fn publish_diagnostics(
+		&self,
+		ctx: &egui::Context,
+		monitor_config: &MonitorConfig,
+	) -> anyhow::Result<()> {
+		let mut host_rect = RECT::default();
+		unsafe { GetWindowRect(self.host, &mut host_rect) }?;
+
+		let taskbar = match self.taskbar {
+			Some(taskbar) => {
+				let mut rect = RECT::default();
+				unsafe { GetWindowRect(taskbar.hwnd, &mut rect) }?;
+				Some((taskbar.hwnd, rect))
+			}
+			None => None,
+		};
+
+		let diagnostics = SwitcherDiagnostics {
+			monitor: format!("{}-{}", self.monitor_state.name, self.monitor_state.id),
+			host: (self.host, host_rect),
+			taskbar,
+			pixels_per_point: ctx.pixels_per_point(),
+			frame_stats: FrameStats::get(ctx),
+			monitor_config: toml::to_string_pretty(monitor_config)?,
+		};
+
+		self.debug_overlay
+			.publish(&self.monitor_state.id, diagnostics);
+
+		Ok(())
+	}
+
 	/// Gets the effective config for the switcher, which is the preview config
 	/// if set, or the actual config otherwise.
 	fn effective_config(&self) -> Config {
@@ -141,12 +251,78 @@ impl SwitcherWindowView {
 		})
 	}
 
-	/// Gets the height of the taskbar.
-	/// Used for auto-sizing the switcher panel to match the taskbar height.
-	fn taskbar_height(&self) -> anyhow::Result<i32> {
+	/// Gets the size of the taskbar, if any.
+	/// Used for auto-sizing the switcher panel to match the taskbar thickness.
+	fn taskbar_size(&self) -> anyhow::Result<Option<(i32, i32)>> {
+		let Some(taskbar) = self.taskbar else {
+			return Ok(None);
+		};
+
+		let hidden = self.taskbar_layout.is_some_and(|l| l.hidden);
+		if let Some(size) = self.shown_taskbar_size.get().filter(|_| hidden) {
+			return Ok(Some(size));
+		}
+
 		let mut rect = RECT::default();
-		unsafe { GetClientRect(self.taskbar.hwnd, &mut rect) }?;
-		Ok(rect.bottom - rect.top)
+		unsafe { GetClientRect(taskbar.hwnd, &mut rect) }?;
+		let size = (rect.right - rect.left, rect.bottom - rect.top);
+
+		if !hidden {
+			self.shown_taskbar_size.set(Some(size));
+		}
+
+		Ok(Some(size))
+	}
+
+	/// Whether the switcher is in a vertical taskbar.
+	fn is_vertical(&self) -> bool {
+		self.taskbar_layout.is_some_and(|l| l.edge.is_vertical())
+	}
+
+	/// Gets the position of the host in the taskbar, resolving the anchor
+	/// against the current taskbar layout if needed.
+	///
+	/// Floating switchers are positioned relative to the monitor instead,
+	/// in screen coordinates, and switchers kept visible while the taskbar is
+	/// hidden are positioned at the monitor edge of the taskbar.
+	fn host_position(
+		&self,
+		monitor_config: &crate::config::MonitorConfig,
+		width: i32,
+		height: i32,
+	) -> anyhow::Result<(i32, i32)> {
+		let Some(layout) = self.taskbar_layout else {
+			let rect = &self.monitor_state.rect;
+			return Ok((rect.left + monitor_config.x, rect.top + monitor_config.y));
+		};
+
+		// The Start button is part of the XAML content of Windows 11 taskbars,
+		// with no window to anchor to
+		if monitor_config.anchor == Anchor::AfterStart
+			&& layout.start_button.is_none()
+			&& !self.after_start_warned.replace(true)
+		{
+			tracing::warn!("Start button not found, `after-start` anchor falls back to `left`");
+			crate::notifications::warn(
+				"The `after-start` anchor isn't supported by this taskbar (e.g. on Windows 11), using `left` instead",
+			);
+		}
+
+		let (x, y) = layout.host_position(monitor_config, width, height);
+
+		if !self.floating_while_hidden {
+			return Ok((x, y));
+		}
+
+		let rect = &self.monitor_state.rect;
+		let position = match layout.edge {
+			TaskbarEdge::Bottom => (rect.left + x, rect.bottom - height),
+			TaskbarEdge::Top => (rect.left + x, rect.top),
+			TaskbarEdge::Left => (rect.left, rect.top + y),
+			TaskbarEdge::Right => (rect.right - width, rect.top + y),
+		};
+
+		Ok(position)
 	}
 
 	/// Determines if the system is in dark mode.
@@ -157,6 +333,25 @@ impl SwitcherWindowView {
 			.unwrap_or(false)
 	}
 
+	/// Resolves a color expression from the config against the system colors.
+	fn resolve_color(&self, color: &str) -> Option<egui::Color32> {
+		egui_color_from_color(color, &self.system_colors)
+	}
+
+	/// Resolves the colors of the switcher against the system colors.
+	fn switcher_colors(&self, colors: &ColorsConfig) -> SwitcherColors {
+		let resolve = |color: &Option<String>| color.as_deref().and_then(|c| self.resolve_color(c));
+
+		SwitcherColors {
+			text: resolve(&colors.text).or(self.forgreound_color),
+			active_indicator: resolve(&colors.active_indicator),
+			busy_indicator: resolve(&colors.busy_indicator),
+			unfocused_active_indicator: resolve(&colors.unfocused_active_indicator),
+			focus_bar: resolve(&colors.focus_bar),
+			buttons: ButtonColors::new(colors, &self.system_colors),
+		}
+	}
+
 	/// Gets the accent color to use for the switcher, based on the system
 	/// accent color and the current theme (light/dark mode).
 	fn accent_color(&self) -> Option<egui::Color32> {
@@ -179,8 +374,18 @@ impl SwitcherWindowView {
 		unsafe { menu.show_context_menu_for_hwnd(hwnd, None) };
 	}
 
-	/// Updates system colors from Windows settings, and stores them in the view
-	/// state.
+	/// Inspects the taskbar elements again, and forces repositioning the host.
+	fn refresh_taskbar_layout(&mut self) -> anyhow::Result<()> {
+		self.taskbar_layout = self
+			.taskbar
+			.map(|taskbar| TaskbarLayout::new(taskbar.hwnd))
+			.transpose()?;
+		self.prev_bounds = None;
+		Ok(())
+	}
+
+	/// Updates system colors and the animations preference from Windows
+	/// settings, and stores them in the view state.
 	fn update_system_colors(&mut self) -> anyhow::Result<()> {
 		let settings = UISettings::new()?;
 
@@ -196,6 +401,57 @@ impl SwitcherWindowView {
 		let color = egui::Color32::from_rgb(color.R, color.G, color.B);
 		self.forgreound_color.replace(color);
 
+		let get_color = |color_type| -> anyhow::Result<Option<color_expr::Color>> {
+			let color = settings.GetColorValue(color_type)?;
+			let color = color_expr::Color::from_rgba8(color.R, color.G, color.B, color.A);
+			Ok(Some(color))
+		};
+
+		self.system_colors = SystemColors {
+			accent: get_color(UIColorType::Accent)?,
+			accent_light: [
+				get_color(UIColorType::AccentLight1)?,
+				get_color(UIColorType::AccentLight2)?,
+				get_color(UIColorType::AccentLight3)?,
+			],
+			accent_dark: [
+				get_color(UIColorType::AccentDark1)?,
+				get_color(UIColorType::AccentDark2)?,
+				get_color(UIColorType::AccentDark3)?,
+			],
+			foreground: get_color(UIColorType::Foreground)?,
+			background: get_color(UIColorType::Background)?,
+		};
+
+		self.system_animations_enabled = settings.AnimationsEnabled()?;
+
+		Ok(())
+	}
+
+	/// Moves the host out of the auto-hidden taskbar while it is hidden if
+	/// `keep_visible` is enabled, and back into the taskbar when it shows.
+	fn update_floating_while_hidden(
+		&mut self,
+		monitor_config: &crate::config::MonitorConfig,
+	) -> anyhow::Result<()> {
+		let Some(taskbar) = self.taskbar else {
+			return Ok(());
+		};
+
+		let hidden = self.taskbar_layout.is_some_and(|l| l.hidden);
+		let floating = monitor_config.keep_visible && hidden;
+		if floating == self.floating_while_hidden {
+			return Ok(());
+		}
+
+		tracing::debug!("Taskbar hidden: {floating}, moving host accordingly");
+
+		unsafe { host::set_floating(self.host, taskbar.hwnd, floating) }?;
+		self.floating_while_hidden = floating;
+
+		// Force repositioning the host
+		self.prev_bounds = None;
+
 		Ok(())
 	}
 
@@ -210,21 +466,29 @@ impl SwitcherWindowView {
 		// Scale by pixels per point
 		let rect = rect * ppp;
 
-		let x = monitor_config.x;
-		let y = monitor_config.y;
+		self.update_floating_while_hidden(monitor_config)?;
 
-		let height = if monitor_config.auto_height {
-			self.taskbar_height()?
-		} else {
-			monitor_config.height
+		// Auto size matches the taskbar thickness on the cross axis,
+		// and the content on the main axis, or the content on both axes
+		// for floating switchers.
+		let content_size = (rect.width() as i32, rect.height() as i32);
+		let (taskbar_width, taskbar_height) = self.taskbar_size()?.unwrap_or(content_size);
+		let vertical = self.is_vertical();
+
+		let height = match (monitor_config.auto_height, vertical) {
+			(true, false) => taskbar_height,
+			(true, true) => rect.height() as i32,
+			(false, _) => monitor_config.height,
 		};
 
-		let width = if monitor_config.auto_width {
-			rect.width() as i32
-		} else {
-			monitor_config.width
+		let width = match (monitor_config.auto_width, vertical) {
+			(true, false) => rect.width() as i32,
+			(true, true) => taskbar_width,
+			(false, _) => monitor_config.width,
 		};
 
+		let (x, y) = self.host_position(monitor_config, width, height)?;
+
 		let current_bounds = egui::Rect::from_min_size(
 			egui::pos2(x as f32, y as f32),
 			egui::vec2(width as f32, height as f32),
@@ -263,20 +527,16 @@ impl SwitcherWindowView {
 	fn maybe_apply_font(
 		&mut self,
 		ctx: &egui::Context,
-		config: &Config,
 		monitor_config: &crate::config::MonitorConfig,
 	) {
-		let font_family = monitor_config
-			.font_family
-			.as_deref()
-			.or(config.font_family.as_deref());
-		let font_weight = monitor_config
-			.font_weight
-			.or(config.font_weight)
-			.unwrap_or(400);
+		let font_family = monitor_config.font_family.as_ref();
+		let font_weight = monitor_config.font_weight.unwrap_or(400);
 
 		// Skip if the desired font is already applied
-		let desired = font_family.map(|family| (family.to_string(), font_weight));
+		let desired = font_family.map(|family| {
+			let families = family.families().into_iter().map(String::from).collect();
+			(families, font_weight)
+		});
 		if self.applied_font == desired {
 			return;
 		}
@@ -284,34 +544,36 @@ impl SwitcherWindowView {
 		// Update applied font to avoid redundant updates next time
 		self.applied_font = desired.clone();
 
-		// Load font data for the desired font, if specified. If loading fails, log a
-		// warning and fall back to default font.
-		let font_data = desired.as_ref().and_then(|(family, weight)| {
-			let font = crate::utils::find_font(family, *weight);
+		// Load font data for each of the desired fonts, if specified. If loading
+		// fails, log a warning and skip it, falling back to the next font in the
+		// chain and eventually to the default fonts.
+		let (families, weight) = desired.unwrap_or_default();
+		let fonts_data = families.iter().filter_map(|family| {
+			let font = crate::utils::find_font(family, weight);
 			let data = font.and_then(|f| f.copy_font_data());
 			let data = data.map(|arc| arc.to_vec());
 			if data.is_none() {
-				tracing::warn!(
-					"Font '{family}' with weight {weight} not found, falling back to default font"
-				);
+				tracing::warn!("Font '{family}' with weight {weight} not found, skipping it");
+				crate::notifications::warn(format!(
+					"Font '{family}' with weight {weight} not found, using a fallback font"
+				));
 			}
 			data
 		});
 
-		// Set font in egui context, or fall back to default.
+		// Set fonts in egui context in fallback order, before the default fonts.
 		let mut fonts = egui::FontDefinitions::default();
-		if let Some(data) = font_data {
-			const SWITCHER_FONT_NAME: &str = "switcher_custom";
+		for (idx, data) in fonts_data.enumerate() {
+			let font_name = format!("switcher_custom_{idx}");
 
-			fonts.font_data.insert(
-				SWITCHER_FONT_NAME.to_owned(),
-				egui::FontData::from_owned(data).into(),
-			);
+			fonts
+				.font_data
+				.insert(font_name.clone(), egui::FontData::from_owned(data).into());
 			fonts
 				.families
 				.entry(egui::FontFamily::Proportional)
 				.or_default()
-				.insert(0, SWITCHER_FONT_NAME.to_owned());
+				.insert(idx, font_name);
 		}
 		ctx.set_fonts(fonts);
 	}
@@ -319,10 +581,18 @@ impl SwitcherWindowView {
 
 /// UI
 impl SwitcherWindowView {
-	fn layout_button(&self, ui: &mut egui::Ui, focused_workspace: &crate::komorebi::Workspace) {
+	fn layout_button(
+		&self,
+		ui: &mut egui::Ui,
+		focused_workspace: &crate::komorebi::Workspace,
+		metrics: ButtonMetrics,
+		colors: &SwitcherColors,
+	) {
 		let btn = LayoutButton::new(&focused_workspace.layout)
 			.dark_mode(Some(self.is_system_dark_mode()))
-			.text_color_opt(self.forgreound_color);
+			.metrics(metrics)
+			.colors(colors.buttons)
+			.text_color_opt(colors.text);
 
 		if ui.add(btn).clicked() {
 			crate::komorebi::cycle_layout(CycleDirection::Next);
@@ -333,59 +603,97 @@ impl SwitcherWindowView {
 		&self,
 		ui: &mut egui::Ui,
 		workspace: &crate::komorebi::Workspace,
+		workspace_config: &crate::config::WorkspaceConfig,
+		metrics: ButtonMetrics,
 		monitor_config: &crate::config::MonitorConfig,
+		colors: &SwitcherColors,
 		config: &Config,
 	) {
 		// Determine active indicator colors,
-		// with monitor config taking precedence over global config,
+		// with workspace config taking precedence over the resolved colors,
 		// and falling back to accent color if not specified.
-		let active_indicator_color = match monitor_config.colors.active_indicator {
-			Some(ref c) => egui_color_from_color(c),
-			None => config
-				.colors
-				.active_indicator
-				.as_ref()
-				.and_then(|c| egui_color_from_color(c)),
+		let active_indicator_color = match workspace_config.active_indicator {
+			Some(ref c) => self.resolve_color(c),
+			None => colors.active_indicator,
 		};
 		let active_indicator_color = active_indicator_color.or_else(|| self.accent_color());
 
-		// Determine busy indicator color,
-		// with monitor config taking precedence over global config.
-		let busy_indicator_color = match monitor_config.colors.busy_indicator {
-			Some(ref c) => egui_color_from_color(c),
-			None => config
-				.colors
-				.busy_indicator
-				.as_ref()
-				.and_then(|c| egui_color_from_color(c)),
+		// Override active indicator color when this monitor is not focused.
+		let active_indicator_color = if self.monitor_state.focused {
+			active_indicator_color
+		} else {
+			colors.unfocused_active_indicator.or(active_indicator_color)
+		};
+
+		let indicator_style = monitor_config.indicator_style.unwrap_or_default();
+
+		// Dim indicators when this monitor is not focused.
+		let indicator_opacity = if self.monitor_state.focused {
+			1.0
+		} else {
+			monitor_config.colors.unfocused_opacity.unwrap_or(1.0)
 		};
 
+		// Determine label format,
+		// with workspace config taking precedence over monitor config.
+		let label_format = workspace_config
+			.label
+			.as_deref()
+			.or(monitor_config.label_format.as_deref())
+			.unwrap_or(DEFAULT_LABEL_FORMAT);
+		let icon = config.get_icon(workspace_config, workspace);
+		let label = format_label(label_format, workspace, icon);
+
 		let btn = WorkspaceButton::new(workspace)
+			.label(label)
+			.metrics(metrics)
 			.dark_mode(Some(self.is_system_dark_mode()))
 			.line_active_color_opt(active_indicator_color)
-			.line_busy_color_opt(busy_indicator_color)
-			.text_color_opt(self.forgreound_color);
+			.line_busy_color_opt(colors.busy_indicator)
+			.indicator_opacity(indicator_opacity)
+			.indicator_style(indicator_style)
+			.animation(ButtonAnimation::new(
+				&config.animations,
+				self.system_animations_enabled,
+			))
+			.colors(colors.buttons)
+			.text_color_opt(colors.text);
 
 		if ui.add(btn).clicked() {
 			crate::komorebi::change_workspace(self.monitor_state.index, workspace.index);
 		}
 	}
 
+	/// Dimmed icon shown while komorebi isn't running.
+	fn disconnected_ui(&self, ui: &mut egui::Ui, metrics: ButtonMetrics) {
+		let icon = egui::RichText::new("⚠").size(metrics.font_size);
+		ui.add(egui::Label::new(icon).selectable(false))
+			.on_disabled_hover_text("komorebi not running");
+	}
+
 	/// Main UI elements, workspaces buttons, layout button ...etc
 	fn switcher_ui(
 		&mut self,
 		ui: &mut egui::Ui,
 		config: &Config,
 		monitor_config: &crate::config::MonitorConfig,
+		colors: &SwitcherColors,
 	) {
+		let metrics = &monitor_config.metrics;
+		let button_metrics = ButtonMetrics::new(monitor_config.font_size, metrics);
+
 		// Set spacing between buttons
-		ui.style_mut().spacing.item_spacing = egui::vec2(4., 4.);
+		let spacing = metrics.spacing.unwrap_or(DEFAULT_SPACING);
+		ui.style_mut().spacing.item_spacing = egui::vec2(spacing, spacing);
+
+		// Grey out the last known workspaces while komorebi isn't running,
+		// which also makes them non-clickable
+		if !self.komorebi_connected {
+			ui.disable();
+			self.disconnected_ui(ui, button_metrics);
+		}
 
-		// Determine whether to show or hide empty workspaces
-		let hide_empty_workspaces = match monitor_config.hide_empty_workspaces {
-			Some(hide) => hide,
-			None => config.hide_empty_workspaces,
-		};
+		let hide_empty_workspaces = monitor_config.hide_empty_workspaces.unwrap_or_default();
 
 		// Draw a button for each workspace
 		for workspace in self.monitor_state.workspaces.iter() {
@@ -394,30 +702,78 @@ impl SwitcherWindowView {
 				continue;
 			}
 
-			self.workspace_button(ui, workspace, monitor_config, config);
+			// Skip hidden and unfocused workspaces
+			let workspace_config = monitor_config
+				.workspaces
+				.get(&workspace.name)
+				.cloned()
+				.unwrap_or_default();
+			if workspace_config.hidden == Some(true) && !workspace.focused {
+				continue;
+			}
+
+			self.workspace_button(
+				ui,
+				workspace,
+				&workspace_config,
+				button_metrics,
+				monitor_config,
+				colors,
+				config,
+			);
 		}
 
 		// Show layout button for focused workspace if the setting is enabled
-		let show_layout_button = match monitor_config.show_layout_button {
-			Some(show) => show,
-			None => config.show_layout_button,
-		};
-		if show_layout_button {
+		if monitor_config.show_layout_button.unwrap_or_default() {
 			if let Some(focused_ws) = self.monitor_state.focused_workspace() {
-				ui.add(egui::Label::new("|"));
-
-				self.layout_button(ui, focused_ws);
+				if ui.layout().is_horizontal() {
+					let separator = egui::RichText::new("|").size(button_metrics.font_size);
+					ui.add(egui::Label::new(separator));
+				} else {
+					ui.separator();
+				}
+
+				self.layout_button(ui, focused_ws, button_metrics, colors);
 			}
 		}
 	}
 
-	/// Transparent panel containing the UI elements horizontally with some
-	/// margin around it.
+	/// Draws a bar along the top of the content rect, or its left side if
+	/// `vertical`, if this monitor is focused and a focus bar color is configured.
+	fn focus_bar_ui(
+		&self,
+		ui: &mut egui::Ui,
+		content_rect: egui::Rect,
+		vertical: bool,
+		colors: &SwitcherColors,
+	) {
+		const FOCUS_BAR_HEIGHT: f32 = 2.0;
+
+		if !self.monitor_state.focused {
+			return;
+		}
+
+		let Some(color) = colors.focus_bar else {
+			return;
+		};
+
+		let rect = if vertical {
+			content_rect.with_max_x(content_rect.min.x + FOCUS_BAR_HEIGHT)
+		} else {
+			content_rect.with_max_y(content_rect.min.y + FOCUS_BAR_HEIGHT)
+		};
+		ui.painter()
+			.rect_filled(rect, FOCUS_BAR_HEIGHT / 2.0, color);
+	}
+
+	/// Transparent panel containing the UI elements with some margin around it,
+	/// laid out vertically on vertical taskbars and horizontally otherwise.
 	fn switcher_panel(
 		&mut self,
 		ctx: &egui::Context,
 		config: &Config,
 		monitor_config: &crate::config::MonitorConfig,
+		colors: &SwitcherColors,
 	) -> egui::InnerResponse<egui::Rect> {
 		// Set transparent panel visual for the switcher.
 		let visuals = egui::Visuals {
@@ -431,13 +787,22 @@ impl SwitcherWindowView {
 
 		// Create central panel with the margined frame
 		let total_margin = frame.total_margin();
+		let vertical = self.is_vertical();
 		egui::CentralPanel::default().frame(frame).show(ctx, |ui| {
-			let response = ui.horizontal_centered(|ui| {
-				self.switcher_ui(ui, config, monitor_config);
+			let add_contents = |ui: &mut egui::Ui| {
+				self.switcher_ui(ui, config, monitor_config, colors);
 				ui.min_rect()
-			});
+			};
+
+			let response = if vertical {
+				ui.vertical_centered(add_contents)
+			} else {
+				ui.horizontal_centered(add_contents)
+			};
 
-			// Use the content rect from inside the horizontal layout,
+			self.focus_bar_ui(ui, response.inner, vertical, colors);
+
+			// Use the content rect from inside the layout,
 			// expanded by the frame's total margin (inner + outer + stroke).
 			response.inner.expand2(total_margin.right_bottom())
 		})
@@ -451,25 +816,73 @@ impl EguiView for SwitcherWindowView {
 		_event_loop: &ActiveEventLoop,
 		message: &AppMessage,
 	) -> anyhow::Result<()> {
+		// Only repaint when the state of this switcher changed
 		match message {
 			AppMessage::UpdateKomorebiState(state) => {
-				self.monitor_state = state
+				let monitor_state = state
 					.monitors
 					.iter()
 					.find(|m| m.id == self.monitor_state.id)
 					.cloned()
 					.unwrap_or_default();
+
+				if monitor_state != self.monitor_state || state.theme != self.komorebi_theme {
+					self.monitor_state = monitor_state;
+					self.komorebi_theme = state.theme.clone();
+					ctx.request_repaint();
+				}
+			}
+
+			AppMessage::KomorebiConnectionChanged(connected) => {
+				if *connected != self.komorebi_connected {
+					self.komorebi_connected = *connected;
+					ctx.request_repaint();
+				}
+			}
+
+			AppMessage::PreviewConfig(config) => {
+				if self.preview_config.as_ref() != Some(config) {
+					self.preview_config = Some(config.clone());
+					ctx.request_repaint();
+				}
 			}
 
-			AppMessage::PreviewConfig(config) => self.preview_config = Some(config.clone()),
-			AppMessage::ClearPreviewConfig => self.preview_config = None,
+			AppMessage::ClearPreviewConfig => {
+				if self.preview_config.take().is_some() {
+					ctx.request_repaint();
+				}
+			}
 
-			AppMessage::SystemSettingsChanged => self.update_system_colors()?,
+			AppMessage::SystemSettingsChanged => {
+				self.update_system_colors()?;
+				ctx.request_repaint();
+			}
+
+			// Force repositioning the host, in case it is anchored to a taskbar element
+			AppMessage::TaskbarLayoutChanged { taskbar, layout } => {
+				if self.taskbar.is_some_and(|t| t.hwnd.0 as isize == *taskbar) {
+					self.taskbar_layout = Some(*layout);
+					self.prev_bounds = None;
+					ctx.request_repaint();
+				}
+			}
+
+			AppMessage::EditPosition { monitor } => {
+				if utils::window_monitor(self.host) == *monitor {
+					self.start_edit_position()?;
+					ctx.request_repaint();
+				}
+			}
+
+			// Publish diagnostics to the newly shown overlay
+			AppMessage::ToggleDebugOverlay => ctx.request_repaint(),
 
 			AppMessage::DpiChanged => {
+				self.refresh_taskbar_layout()?;
 				let dpi = unsafe { GetDpiForWindow(self.host) } as f32;
 				let ppp = dpi / USER_DEFAULT_SCREEN_DPI as f32;
 				ctx.set_pixels_per_point(ppp);
+				ctx.request_repaint();
 			}
 
 			_ => {}
@@ -478,6 +891,12 @@ impl EguiView for SwitcherWindowView {
 		Ok(())
 	}
 
+	fn handle_egui_context_recreated(&mut self, _ctx: &egui::Context) {
+		// Fonts and size need to be applied again
+		self.applied_font = None;
+		self.prev_bounds = None;
+	}
+
 	// Main render loop
 	fn update(&mut self, ctx: &egui::Context) {
 		// Show context menu on right click
@@ -487,13 +906,33 @@ impl EguiView for SwitcherWindowView {
 
 		// Load effective config.
 		let config = self.effective_config();
-		let monitor_config = config.get_monitor(&self.monitor_state.id);
+		let monitor_config = config.resolve_monitor(
+			&self.monitor_state.id,
+			&self.monitor_state.workspaces,
+			self.is_system_dark_mode(),
+			self.komorebi_theme.as_ref(),
+		);
+		let colors = self.switcher_colors(&monitor_config.colors);
 
 		// Apply font
-		self.maybe_apply_font(ctx, &config, &monitor_config);
+		self.maybe_apply_font(ctx, &monitor_config);
 
 		// Draw ui
-		let response = self.switcher_panel(ctx, &config, &monitor_config);
+		let response = self.switcher_panel(ctx, &config, &monitor_config, &colors);
+
+		if self.debug_overlay.is_visible() {
+			if let Err(e) = self.publish_diagnostics(ctx, &monitor_config) {
+				tracing::error!("Failed to publish switcher diagnostics: {e}");
+			}
+		}
+
+		// While editing, the host is moved and resized by dragging instead
+		if self.edit.is_some() {
+			if let Err(e) = self.edit_position_ui(ctx, &monitor_config) {
+				tracing::error!("Failed to edit switcher position: {e}");
+			}
+			return;
+		}
 
 		// Resize host to match the content rect.
 		let ppp = ctx.pixels_per_point();
@@ -0,0 +1,231 @@
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use windows::Win32::Foundation::RECT;
+use windows::Win32::UI::WindowsAndMessaging::{
+	SystemParametersInfoW, SPI_GETWORKAREA, SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS,
+};
+use winit::dpi::{PhysicalPosition, PhysicalSize};
+use winit::event_loop::ActiveEventLoop;
+use winit::platform::windows::WindowAttributesExtWindows;
+use winit::window::{Window, WindowAttributes, WindowLevel};
+
+use crate::notifications::{Action, Notification, Severity};
+use crate::windows::app::App;
+use crate::windows::egui_glue::{EguiView, EguiWindow};
+
+/// Time a toast is shown for, unless closed before.
+const TOAST_DURATION: Duration = Duration::from_secs(8);
+
+/// Maximum number of toasts shown at once, the oldest ones are closed first.
+const MAX_TOASTS: usize = 4;
+
+/// Width of the toasts and their margin to the work area edges, in logical pixels.
+const WIDTH: f32 = 360.0;
+const MARGIN: f32 = 12.0;
+
+#[derive(Debug)]
+struct Toast {
+	notification: Notification,
+	shown_at: Instant,
+}
+
+/// Toasts currently shown, shared with the toasts window.
+#[derive(Debug, Clone, Default)]
+pub struct Toasts(Arc<Mutex<VecDeque<Toast>>>);
+
+impl Toasts {
+	fn push(&self, notification: Notification) {
+		let mut toasts = self.0.lock().unwrap();
+		toasts.push_back(Toast {
+			notification,
+			shown_at: Instant::now(),
+		});
+		while toasts.len() > MAX_TOASTS {
+			toasts.pop_front();
+		}
+	}
+}
+
+impl App {
+	/// Shows a notification as a toast at the bottom right of the primary
+	/// monitor's work area, creating the toasts window if needed.
+	pub fn show_notification(
+		&mut self,
+		event_loop: &ActiveEventLoop,
+		notification: Notification,
+	) -> anyhow::Result<()> {
+		self.toasts.push(notification);
+
+		// The window hides itself once all toasts are gone
+		if let Some(window_id) = self.toasts_window {
+			if let Some(window) = self.windows.get_mut(&window_id) {
+				window.set_visible(true);
+				window.surface.egui_ctx().request_repaint();
+				return Ok(());
+			}
+		}
+
+		#[cfg(debug_assertions)]
+		let class_name = "komorebi-switcher-debug::toasts";
+		#[cfg(not(debug_assertions))]
+		let class_name = "komorebi-switcher::toasts";
+
+		// Sized and positioned once the toasts are laid out
+		let attrs = WindowAttributes::default()
+			.with_title("komorebi-switcher")
+			.with_class_name(class_name)
+			.with_inner_size(PhysicalSize::new(1, 1))
+			.with_decorations(false)
+			.with_resizable(false)
+			.with_active(false)
+			.with_skip_taskbar(true)
+			.with_window_level(WindowLevel::AlwaysOnTop)
+			.with_no_redirection_bitmap(self.backend.no_redirection_bitmap());
+
+		let window = event_loop.create_window(attrs)?;
+		let window = Arc::new(window);
+
+		let view = ToastsView {
+			window: window.clone(),
+			toasts: self.toasts.clone(),
+		};
+
+		let window = EguiWindow::new(window, &self.backend, view)?;
+
+		self.toasts_window = Some(window.id());
+		self.windows.insert(window.id(), None, window);
+
+		Ok(())
+	}
+}
+
+struct ToastsView {
+	window: Arc<Window>,
+	toasts: Toasts,
+}
+
+impl ToastsView {
+	fn toast_ui(ui: &mut egui::Ui, toast: &Toast) -> bool {
+		let (color, title) = match toast.notification.severity {
+			Severity::Warning => (ui.visuals().warn_fg_color, "Warning"),
+			Severity::Error => (ui.visuals().error_fg_color, "Error"),
+		};
+
+		let frame = egui::Frame::window(ui.style()).stroke(egui::Stroke::new(1.0, color));
+
+		let response = frame.show(ui, |ui| {
+			ui.set_width(ui.available_width());
+
+			let mut closed = false;
+			ui.horizontal(|ui| {
+				ui.colored_label(color, egui::RichText::new(title).strong());
+				ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+					closed = ui.small_button("✕").clicked();
+				});
+			});
+			ui.label(&toast.notification.message);
+
+			if let Some(action) = &toast.notification.action {
+				let clicked = match action {
+					Action::OpenFolder(_) => ui.button("Open folder").clicked(),
+				};
+
+				if clicked {
+					if let Err(e) = run_action(action) {
+						tracing::error!("Failed to run notification action: {e}");
+					}
+					closed = true;
+				}
+			}
+
+			closed
+		});
+
+		response.inner
+	}
+
+	/// Resizes the window to fit the toasts, and moves it to the bottom right
+	/// of the primary monitor's work area.
+	fn fit_window(&self, content_height: f32, ppp: f32) {
+		let mut work_area = RECT::default();
+		let work_area_ptr = &mut work_area as *mut RECT;
+		let flags = SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS(0);
+		if let Err(e) =
+			unsafe { SystemParametersInfoW(SPI_GETWORKAREA, 0, Some(work_area_ptr as _), flags) }
+		{
+			tracing::error!("Failed to get work area: {e}");
+			return;
+		}
+
+		let width = (WIDTH * ppp).round() as u32;
+		let height = (content_height * ppp).ceil() as u32;
+		let margin = (MARGIN * ppp).round() as i32;
+
+		let size = PhysicalSize::new(width, height);
+		if self.window.inner_size() != size {
+			let _ = self.window.request_inner_size(size);
+		}
+
+		let position = PhysicalPosition::new(
+			work_area.right - width as i32 - margin,
+			work_area.bottom - height as i32 - margin,
+		);
+		if self.window.outer_position().ok() != Some(position) {
+			self.window.set_outer_position(position);
+		}
+	}
+}
+
+impl EguiView for ToastsView {
+	fn update(&mut self, ctx: &egui::Context) {
+		let mut toasts = self.toasts.0.lock().unwrap();
+		toasts.retain(|toast| toast.shown_at.elapsed() < TOAST_DURATION);
+
+		if toasts.is_empty() {
+			self.window.set_visible(false);
+			return;
+		}
+
+		// Wake up to remove the next toast once it expires
+		if let Some(next) = toasts.iter().map(|toast| toast.shown_at).min() {
+			ctx.request_repaint_after(TOAST_DURATION.saturating_sub(next.elapsed()));
+		}
+
+		let response = egui::CentralPanel::default()
+			.frame(egui::Frame::NONE)
+			.show(ctx, |ui| {
+				// Laid out at the toasts width, as the window is only resized after
+				let max_size = egui::vec2(WIDTH, f32::INFINITY);
+				ui.allocate_ui(max_size, |ui| {
+					let mut closed = None;
+					for (idx, toast) in toasts.iter().enumerate() {
+						if Self::toast_ui(ui, toast) {
+							closed = Some(idx);
+						}
+					}
+					(closed, ui.min_rect().height())
+				})
+				.inner
+			});
+
+		let (closed, content_height) = response.inner;
+		if let Some(idx) = closed {
+			toasts.remove(idx);
+			ctx.request_repaint();
+		}
+
+		self.fit_window(content_height, ctx.pixels_per_point());
+	}
+}
+
+fn run_action(action: &Action) -> anyhow::Result<()> {
+	match action {
+		Action::OpenFolder(path) => {
+			std::process::Command::new("explorer").arg(path).spawn()?;
+		}
+	}
+
+	Ok(())
+}